@@ -2,11 +2,20 @@
 #![allow(clippy::missing_safety_doc)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::ffi::CString;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::Read;
 use std::ops::Deref;
 use std::path::Path;
 
 use biodivine_lib_bdd::Bdd;
+use biodivine_lib_bdd::BddNode;
+use biodivine_lib_bdd::BddPartialValuation;
+use biodivine_lib_bdd::BddPointer;
 use biodivine_lib_bdd::BddVariable;
 use biodivine_lib_bdd::BddVariableSet;
 
@@ -30,23 +39,182 @@ pub struct bdd_assignment_t {
     len: usize,
 }
 
+impl bdd_assignment_t {
+    fn null() -> Self {
+        bdd_assignment_t {
+            data: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn from_vec(mut assignment: Vec<OptBool>) -> Self {
+        assignment.shrink_to_fit();
+        let len = assignment.len();
+        let data = assignment.as_mut_ptr() as *mut i8;
+        std::mem::forget(assignment);
+        bdd_assignment_t { data, len }
+    }
+}
+
+extern "C" {
+    /// The C library's `free()`, for arrays allocated by the caller
+    fn free(ptr: *mut std::ffi::c_void);
+}
+
 /// Free the given assignment
 ///
 /// To uphold Rust's invariants, all values in the assignment must be 0, 1, or
 /// -1.
 #[no_mangle]
 pub unsafe extern "C" fn bdd_assignment_free(assignment: bdd_assignment_t) {
-    if !assignment.data.is_null() {
-        drop(unsafe {
-            Vec::from_raw_parts(
-                assignment.data as *mut OptBool,
-                assignment.len,
-                assignment.len,
-            )
+    guard((), || {
+        if !assignment.data.is_null() {
+            drop(unsafe {
+                Vec::from_raw_parts(
+                    assignment.data as *mut OptBool,
+                    assignment.len,
+                    assignment.len,
+                )
+            })
+        }
+    })
+}
+
+/// Free `num` assignments stored in `arr` as well as `arr` itself
+///
+/// Each assignment is freed as in `bdd_assignment_free()`, entries with a null
+/// `data` pointer are skipped. `arr` must have been allocated using C's
+/// `malloc()` (or `calloc()`/`realloc()`). If `arr` is null, nothing happens.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_assignment_array_free(arr: *mut bdd_assignment_t, num: usize) {
+    guard((), || {
+        if arr.is_null() {
+            return;
+        }
+        for i in 0..num {
+            unsafe { bdd_assignment_free(arr.add(i).read()) };
+        }
+        unsafe { free(arr as *mut std::ffi::c_void) };
+    })
+}
+
+/// Read an assignment of `num` values from C
+///
+/// Returns `None` if there is a value other than 0, 1, or -1.
+unsafe fn read_assignment(values: *const i8, num: usize) -> Option<Vec<Option<bool>>> {
+    unsafe { &*std::ptr::slice_from_raw_parts(values, num) }
+        .iter()
+        .map(|&v| match v {
+            -1 => Some(None),
+            0 => Some(Some(false)),
+            1 => Some(Some(true)),
+            _ => None,
         })
+        .collect()
+}
+
+fn partial_valuation(values: &[Option<bool>]) -> BddPartialValuation {
+    let mut valuation = BddPartialValuation::empty();
+    for (i, v) in values.iter().enumerate() {
+        if let Some(v) = *v {
+            valuation.set_value(BddVariable::from_index(i), v);
+        }
+    }
+    valuation
+}
+
+/// SplitMix64 pseudo-random number generator
+///
+/// We implement this here to guarantee that seeded results do not change with
+/// dependency updates.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniformly distributed value in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Convert a path given as C string
+///
+/// On Unix, paths may be arbitrary byte strings. Elsewhere, they must be valid
+/// UTF-8.
+unsafe fn c_path<'a>(path: *const std::ffi::c_char) -> Result<&'a Path, String> {
+    let path = unsafe { std::ffi::CStr::from_ptr(path) };
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Ok(Path::new(std::ffi::OsStr::from_bytes(path.to_bytes())))
+    }
+    #[cfg(not(unix))]
+    path.to_str()
+        .map(Path::new)
+        .map_err(|_| "Path is not valid UTF-8".to_string())
+}
+
+// Error handling
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Record `msg` as the last error on this thread
+fn set_error(msg: impl Into<String>) {
+    let msg = CString::new(msg.into().replace('\0', "")).unwrap();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+}
+
+/// Run `body`, turning a panic into the return value `on_panic`
+///
+/// Unwinding across the FFI boundary is undefined behavior, so all entry points
+/// (except for the error reporting functions themselves) are wrapped in this.
+/// The panic message becomes the last error. Output parameters may not have
+/// been written in case of a panic.
+fn guard<T>(on_panic: T, body: impl FnOnce() -> T) -> T {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(body)) {
+        Ok(res) => res,
+        Err(payload) => {
+            let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+                msg
+            } else if let Some(msg) = payload.downcast_ref::<String>() {
+                msg.as_str()
+            } else {
+                "unknown cause"
+            };
+            set_error(format!("Internal error: {msg}"));
+            on_panic
+        }
     }
 }
 
+/// Message describing the most recent error on this thread
+///
+/// Returns null if there was no error since the last call to
+/// `bdd_clear_error()`. The string is owned by the library and remains valid
+/// until the next failing call on this thread or `bdd_clear_error()`.
+#[no_mangle]
+pub extern "C" fn bdd_last_error_message() -> *const std::ffi::c_char {
+    LAST_ERROR.with(|e| match &*e.borrow() {
+        Some(msg) => msg.as_ptr(),
+        None => std::ptr::null(),
+    })
+}
+
+/// Reset the last error of this thread
+#[no_mangle]
+pub extern "C" fn bdd_clear_error() {
+    LAST_ERROR.with(|e| *e.borrow_mut() = None);
+}
+
 // BDD manager & BDD functions
 
 struct Manager {
@@ -54,6 +222,17 @@ struct Manager {
     rc: usize,
     nodes_total: usize,
     max_nodes_total: usize,
+    /// Whether to print a message before aborting due to the node limit
+    verbose: bool,
+    /// Lazily created handles for false and true (indexed by the value)
+    constants: [Option<bdd_t>; 2],
+    stats: manager_stats_t,
+    cache: OpCache,
+    /// Named BDDs loaded using `manager_load()`
+    named: Vec<(CString, Bdd)>,
+    /// Variable names loaded using `manager_load()`, `None` (or missing) for
+    /// anonymous variables
+    var_names: Vec<Option<CString>>,
 }
 
 impl Manager {
@@ -63,6 +242,57 @@ impl Manager {
             rc: 1,
             nodes_total: 0,
             max_nodes_total,
+            verbose: true,
+            constants: [None; 2],
+            stats: manager_stats_t::default(),
+            cache: OpCache::default(),
+            named: Vec::new(),
+            var_names: Vec::new(),
+        }
+    }
+
+    fn add_nodes(&mut self, nodes: usize) {
+        self.nodes_total += nodes;
+        self.stats.nodes_created += nodes as u64;
+        self.stats.peak_nodes = self.stats.peak_nodes.max(self.nodes_total);
+        if self.nodes_total > self.max_nodes_total {
+            if self.verbose {
+                eprintln!(
+                    "Too many nodes ({} > {})",
+                    self.nodes_total, self.max_nodes_total
+                );
+            }
+            std::process::abort();
+        }
+    }
+
+    /// Get a new reference to the cached handle for `value`
+    ///
+    /// The reference owned by the cache does not keep the manager alive, but
+    /// the references handed out do (see `bdd_ref()` and `bdd_unref()`).
+    unsafe fn constant(this: *mut Manager, value: bool) -> bdd_t {
+        let m = unsafe { &mut *this };
+        let f = match m.constants[value as usize] {
+            Some(f) => f,
+            None => {
+                let bdd = if value { m.mk_true() } else { m.mk_false() };
+                let f = unsafe { bdd_t::from_bdd(bdd, this) };
+                unsafe { &mut *f._p }.cached = true;
+                m.rc -= 1;
+                m.constants[value as usize] = Some(f);
+                f
+            }
+        };
+        unsafe { bdd_ref(f) }
+    }
+}
+
+impl Drop for Manager {
+    fn drop(&mut self) {
+        for f in self.constants.iter().flatten() {
+            let f = unsafe { Box::from_raw(f._p) };
+            debug_assert_eq!(f.rc, 1);
+            self.nodes_total -= f.size();
         }
     }
 }
@@ -75,16 +305,157 @@ impl Deref for Manager {
     }
 }
 
+/// Binary operations whose results may be cached
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum BinOp {
+    And,
+    Or,
+    Xor,
+    Imp,
+    Iff,
+    AndNot,
+}
+
+impl BinOp {
+    fn is_commutative(self) -> bool {
+        matches!(self, BinOp::And | BinOp::Or | BinOp::Xor | BinOp::Iff)
+    }
+
+    fn apply(self, f: &Bdd, g: &Bdd) -> Bdd {
+        match self {
+            BinOp::And => f.and(g),
+            BinOp::Or => f.or(g),
+            BinOp::Xor => f.xor(g),
+            BinOp::Imp => f.imp(g),
+            BinOp::Iff => f.iff(g),
+            BinOp::AndNot => f.and_not(g),
+        }
+    }
+}
+
+/// Memoization cache for binary operations (see `manager_set_cache_size()`)
+///
+/// The cache is a direct-mapped table: each key has a single slot, and a new
+/// entry replaces the slot's previous one. Computing a key hashes both
+/// operands, and a lookup compares them with the stored ones, so both take time
+/// linear in the operands' sizes.
+#[derive(Default)]
+struct OpCache {
+    /// Number of slots, 0 disables the cache
+    capacity: usize,
+    /// Allocated on first use
+    slots: Vec<Option<Box<CacheEntry>>>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Result of a binary operation, stored with the operands to detect hash
+/// collisions
+struct CacheEntry {
+    op: BinOp,
+    f: Bdd,
+    g: Bdd,
+    res: Bdd,
+}
+
+impl OpCache {
+    fn apply(&mut self, op: BinOp, f: &Bdd, g: &Bdd) -> Bdd {
+        if self.capacity == 0 {
+            return op.apply(f, g);
+        }
+        fn hash(value: impl Hash) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+        let (mut f, mut g) = (f, g);
+        let (mut f_hash, mut g_hash) = (hash(f), hash(g));
+        if op.is_commutative() && g_hash < f_hash {
+            (f, g) = (g, f);
+            (f_hash, g_hash) = (g_hash, f_hash);
+        }
+        let slot = (hash((op, f_hash, g_hash)) % self.capacity as u64) as usize;
+        if self.slots.is_empty() {
+            self.slots.resize_with(self.capacity, || None);
+        }
+        if let Some(entry) = &self.slots[slot] {
+            if entry.op == op && entry.f == *f && entry.g == *g {
+                self.hits += 1;
+                return entry.res.clone();
+            }
+        }
+        self.misses += 1;
+        let res = op.apply(f, g);
+        self.slots[slot] = Some(Box::new(CacheEntry {
+            op,
+            f: f.clone(),
+            g: g.clone(),
+            res: res.clone(),
+        }));
+        res
+    }
+}
+
+/// Operation statistics of a manager (see `manager_stats()`)
+///
+/// Each counter gives the number of calls to the corresponding BDD functions.
+/// `exists` and `forall` include the single-variable and variable set variants,
+/// `and_exists` etc. include the variable set variants, and `rename` covers the
+/// renaming functions, `bdd_permute()`, `bdd_shift_vars()`, and
+/// `bdd_swap_vars()`. `cofactor` counts `bdd_eval_partial()`,
+/// `bdd_cofactor_const()`, `bdd_simplify()`, and `bdd_constrain()`.
+///
+/// Derived operations count towards the operations they are built on:
+/// `bdd_and_satcount()` counts as `and`, `bdd_majority()` as `ite`,
+/// `bdd_xor_cube()` as `xor`, `bdd_all_equal()` as `iff`, `bdd_setminus()` as
+/// `and_not`, `bdd_xor_satcount()` as `xor`, and `bdd_and_mixed_quant()` as
+/// both `and_exists` and `forall`. `bdd_eval_tree()` counts each operation it
+/// applies.
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct manager_stats_t {
+    not: u64,
+    and: u64,
+    or: u64,
+    xor: u64,
+    imp: u64,
+    iff: u64,
+    and_not: u64,
+    ite: u64,
+    exists: u64,
+    forall: u64,
+    and_exists: u64,
+    or_exists: u64,
+    and_forall: u64,
+    or_forall: u64,
+    rename: u64,
+    cofactor: u64,
+    /// Total number of nodes of all BDDs created
+    nodes_created: u64,
+    /// Maximum value of `manager_node_count()`
+    peak_nodes: usize,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct manager_t {
     _p: *mut Manager,
 }
 
+impl manager_t {
+    fn null() -> Self {
+        manager_t {
+            _p: std::ptr::null_mut(),
+        }
+    }
+}
+
 struct RcBdd {
     bdd: Bdd,
     rc: usize,
     manager: *mut Manager,
+    /// Whether this is one of the manager's constants
+    cached: bool,
 }
 
 impl RcBdd {
@@ -93,6 +464,7 @@ impl RcBdd {
             bdd,
             rc: 1,
             manager,
+            cached: false,
         }
     }
 }
@@ -112,6 +484,12 @@ pub struct VarPair {
     second: u16,
 }
 
+/// BDD handle
+///
+/// Functions that may fail return a handle with a null pointer in case of an
+/// error, `bdd_last_error_message()` describes the error then. This includes
+/// combining BDDs of different managers. It is safe to pass such a handle to
+/// `bdd_unref()`.
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct bdd_t {
@@ -119,367 +497,4900 @@ pub struct bdd_t {
 }
 
 impl bdd_t {
+    fn null() -> Self {
+        bdd_t {
+            _p: std::ptr::null_mut(),
+        }
+    }
+
+    /// Null handle, recording `msg` as the last error
+    fn error(msg: impl Into<String>) -> Self {
+        set_error(msg);
+        Self::null()
+    }
+
     unsafe fn from_bdd(bdd: Bdd, manager: *mut Manager) -> Self {
         let m = unsafe { &mut *manager };
         m.rc += 1;
-        m.nodes_total += bdd.size();
-        if m.nodes_total > m.max_nodes_total {
-            eprintln!("Too many nodes ({} > {})", m.nodes_total, m.max_nodes_total);
-            std::process::abort();
-        }
+        m.add_nodes(bdd.size());
         bdd_t {
             _p: Box::into_raw(Box::new(RcBdd::new(bdd, manager))),
         }
     }
 }
 
-#[no_mangle]
-pub extern "C" fn manager_new(num_vars: u16, max_nodes_total: usize) -> manager_t {
-    let var_set = BddVariableSet::new_anonymous(num_vars);
-    manager_t {
-        _p: Box::into_raw(Box::new(Manager::new(var_set, max_nodes_total))),
+/// Whether all non-null handles in `fs` belong to the same manager
+///
+/// Otherwise, the last error describes the first mismatch.
+unsafe fn same_manager(fs: &[bdd_t]) -> bool {
+    let mut managers = fs
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| !f._p.is_null())
+        .map(|(i, f)| (i, unsafe { &*f._p }.manager));
+    let Some((first, manager)) = managers.next() else {
+        return true;
+    };
+    match managers.find(|&(_, m)| m != manager) {
+        Some((i, _)) => {
+            set_error(format!(
+                "Operand {i} belongs to a different manager than operand {first}"
+            ));
+            false
+        }
+        None => true,
     }
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_ref(manager: manager_t) -> manager_t {
-    unsafe { &mut *manager._p }.rc += 1;
-    manager
+/// `Bdd` for the literal `var` (if `value`) or `¬var` with `num_vars` variables
+///
+/// This is independent of the manager's variable set and thus also works for
+/// BDDs that were loaded from files.
+fn literal(num_vars: u16, var: BddVariable, value: bool) -> Bdd {
+    let mut builder = BddBuilder::new(num_vars);
+    let root = if value {
+        builder.mk_node(var, BddPointer::zero(), BddPointer::one())
+    } else {
+        builder.mk_node(var, BddPointer::one(), BddPointer::zero())
+    };
+    builder.build(root)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_unref(manager: manager_t) {
-    let rc = &mut unsafe { &mut *manager._p }.rc;
-    if *rc == 1 {
-        std::mem::drop(unsafe { Box::from_raw(manager._p) });
-    } else {
-        *rc -= 1;
+/// Conjunction of `literals`, which must be sorted by variable
+fn cube(num_vars: u16, literals: &[(BddVariable, bool)]) -> Bdd {
+    let mut builder = BddBuilder::new(num_vars);
+    let mut root = BddPointer::one();
+    for &(var, value) in literals.iter().rev() {
+        root = if value {
+            builder.mk_node(var, BddPointer::zero(), root)
+        } else {
+            builder.mk_node(var, root, BddPointer::zero())
+        };
     }
+    builder.build(root)
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_node_count(manager: manager_t) -> usize {
-    unsafe { &*manager._p }.nodes_total
+/// Fraction of all assignments that satisfy the function of each node in `f`
+///
+/// Unlike cardinalities, these values do not depend on the node's level and do
+/// not overflow.
+fn node_densities(f: &Bdd) -> Vec<f64> {
+    // Parents are stored after their children
+    let mut densities: Vec<f64> = Vec::with_capacity(f.size());
+    for p in (0..f.size()).map(BddPointer::from_index) {
+        let density = if p.is_zero() {
+            0.0
+        } else if p.is_one() {
+            1.0
+        } else {
+            (densities[f.low_link_of(p).to_index()] + densities[f.high_link_of(p).to_index()]) / 2.0
+        };
+        densities.push(density);
+    }
+    densities
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_ithvar(manager: manager_t, i: u16) -> bdd_t {
-    let bdd = unsafe { &*(manager._p) }.mk_var(BddVariable::from_index(i as usize));
-    unsafe { bdd_t::from_bdd(bdd, manager._p) }
+/// Hash-consing node table to construct reduced BDDs bottom-up
+///
+/// LibBDD compares BDDs structurally, so `build()` emits the nodes in the same
+/// (high-first DFS post-) order as LibBDD's `apply`.
+struct BddBuilder {
+    nodes: Vec<BddNode>,
+    unique: HashMap<BddNode, BddPointer>,
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_nithvar(manager: manager_t, i: u16) -> bdd_t {
-    let bdd = unsafe { &*(manager._p) }.mk_not_var(BddVariable::from_index(i as usize));
-    unsafe { bdd_t::from_bdd(bdd, manager._p) }
+impl BddBuilder {
+    fn new(num_vars: u16) -> Self {
+        Self {
+            nodes: vec![BddNode::mk_zero(num_vars), BddNode::mk_one(num_vars)],
+            unique: HashMap::new(),
+        }
+    }
+
+    fn mk_node(&mut self, var: BddVariable, low: BddPointer, high: BddPointer) -> BddPointer {
+        if low == high {
+            return low;
+        }
+        let node = BddNode::mk_node(var, low, high);
+        if let Some(&p) = self.unique.get(&node) {
+            return p;
+        }
+        let p = BddPointer::from_index(self.nodes.len());
+        self.nodes.push(node);
+        self.unique.insert(node, p);
+        p
+    }
+
+    fn build(self, root: BddPointer) -> Bdd {
+        if root.is_zero() {
+            return Bdd::from_nodes(&self.nodes[..1]).unwrap();
+        }
+        let mut new_ptr: Vec<Option<BddPointer>> = vec![None; self.nodes.len()];
+        new_ptr[0] = Some(BddPointer::zero());
+        new_ptr[1] = Some(BddPointer::one());
+        let mut result = vec![self.nodes[0], self.nodes[1]];
+        let mut stack = vec![root];
+        while let Some(&p) = stack.last() {
+            if new_ptr[p.to_index()].is_some() {
+                stack.pop();
+                continue;
+            }
+            let node = self.nodes[p.to_index()];
+            let low = new_ptr[node.low_link.to_index()];
+            let high = new_ptr[node.high_link.to_index()];
+            if let (Some(low), Some(high)) = (low, high) {
+                new_ptr[p.to_index()] = Some(BddPointer::from_index(result.len()));
+                result.push(BddNode::mk_node(node.var, low, high));
+                stack.pop();
+            } else {
+                if low.is_none() {
+                    stack.push(node.low_link);
+                }
+                if high.is_none() {
+                    stack.push(node.high_link);
+                }
+            }
+        }
+        Bdd::from_nodes(&result).unwrap()
+    }
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn manager_true(manager: manager_t) -> bdd_t {
-    let bdd = unsafe { &*(manager._p) }.mk_true();
-    unsafe { bdd_t::from_bdd(bdd, manager._p) }
+pub extern "C" fn manager_new(num_vars: u16, max_nodes_total: usize) -> manager_t {
+    guard(manager_t::null(), || {
+        let var_set = BddVariableSet::new_anonymous(num_vars);
+        manager_t {
+            _p: Box::into_raw(Box::new(Manager::new(var_set, max_nodes_total))),
+        }
+    })
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn manager_false(manager: manager_t) -> bdd_t {
-    let bdd = unsafe { &*(manager._p) }.mk_false();
-    unsafe { bdd_t::from_bdd(bdd, manager._p) }
+/// Name each anonymous variable `x_i` for its index `i`, appending `'` until
+/// the name differs from all others
+fn complete_var_names(names: Vec<Option<String>>) -> Vec<String> {
+    let mut taken: std::collections::HashSet<String> = names.iter().flatten().cloned().collect();
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            name.unwrap_or_else(|| {
+                let mut name = format!("x_{i}");
+                while taken.contains(&name) {
+                    name.push('\'');
+                }
+                taken.insert(name.clone());
+                name
+            })
+        })
+        .collect()
 }
 
+/// Create a manager with `additional_vars` more variables than `manager`
+///
+/// LibBDD cannot extend a variable set in place, so this returns a new manager
+/// with the same node limit and verbosity while `manager` stays valid. The
+/// existing variables keep their names, the new ones are named `x_i` for their
+/// index `i` (with `'` appended until the name is unique) and are anonymous
+/// (see `manager_var_name()`). BDDs of `manager` can be moved to the new one
+/// using `bdd_transfer()`. Returns a null manager if the new variable count
+/// exceeds the maximum supported by LibBDD.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_ref(f: bdd_t) -> bdd_t {
-    unsafe { &mut *f._p }.rc += 1;
-    f
+pub unsafe extern "C" fn manager_extend(manager: manager_t, additional_vars: u16) -> manager_t {
+    guard(manager_t::null(), || {
+        let m = unsafe { &*manager._p };
+        match m.num_vars().checked_add(additional_vars) {
+            Some(num_vars) if num_vars < u16::MAX - 1 => {
+                let names: Vec<Option<String>> = (0..num_vars as usize)
+                    .map(|i| {
+                        (i < m.num_vars() as usize).then(|| m.name_of(BddVariable::from_index(i)))
+                    })
+                    .collect();
+                let names = complete_var_names(names);
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                let mut new = Manager::new(BddVariableSet::new(&names), m.max_nodes_total);
+                new.verbose = m.verbose;
+                new.var_names = m.var_names.clone();
+                manager_t {
+                    _p: Box::into_raw(Box::new(new)),
+                }
+            }
+            _ => {
+                set_error(format!(
+                    "Too many variables ({} + {additional_vars})",
+                    m.num_vars()
+                ));
+                manager_t::null()
+            }
+        }
+    })
 }
+
+/// Create an independent manager with the same variables as `manager`
+///
+/// The new manager copies the variables including their names, the node limit,
+/// the verbosity, and the cache size, but starts without any nodes, statistics,
+/// or named BDDs.
+/// In contrast to `manager_ref()`, both managers must be freed separately.
+/// BDDs of `manager` can be moved to the new one using `bdd_transfer()`.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_unref(f: bdd_t) {
-    let bdd = unsafe { &mut *f._p };
-    if bdd.rc == 1 {
-        unsafe { &mut *bdd.manager }.nodes_total -= bdd.size();
-        unsafe { manager_unref(manager_t { _p: bdd.manager }) };
-        drop(unsafe { Box::from_raw(f._p) });
-    } else {
-        bdd.rc -= 1;
-    }
+pub unsafe extern "C" fn manager_clone(manager: manager_t) -> manager_t {
+    guard(manager_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let mut new = Manager::new(m.var_set.clone(), m.max_nodes_total);
+        new.verbose = m.verbose;
+        new.cache.capacity = m.cache.capacity;
+        new.var_names = m.var_names.clone();
+        manager_t {
+            _p: Box::into_raw(Box::new(new)),
+        }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_not(f: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let bdd = f.not();
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_ref(manager: manager_t) -> manager_t {
+    guard(manager_t::null(), || {
+        unsafe { &mut *manager._p }.rc += 1;
+        manager
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_and(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.and(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_unref(manager: manager_t) {
+    guard((), || {
+        let rc = &mut unsafe { &mut *manager._p }.rc;
+        if *rc == 1 {
+            std::mem::drop(unsafe { Box::from_raw(manager._p) });
+        } else {
+            *rc -= 1;
+        }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_or(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.or(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_node_count(manager: manager_t) -> usize {
+    guard(0, || unsafe { &*manager._p }.nodes_total)
 }
 
+/// Set whether a message is printed to stderr when the node limit is exceeded
+///
+/// Managers are verbose by default.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_xor(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.xor(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_set_verbose(manager: manager_t, verbose: bool) {
+    guard((), || {
+        unsafe { &mut *manager._p }.verbose = verbose;
+    })
 }
 
+/// Whether `a` and `b` refer to the same manager
 #[no_mangle]
-pub unsafe extern "C" fn bdd_imp(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.imp(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub extern "C" fn manager_eq(a: manager_t, b: manager_t) -> bool {
+    guard(false, || a._p == b._p)
 }
 
+/// Copy the manager's operation statistics to `*out`
 #[no_mangle]
-pub unsafe extern "C" fn bdd_iff(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.iff(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_stats(manager: manager_t, out: *mut manager_stats_t) {
+    guard((), || {
+        unsafe { *out = (*manager._p).stats };
+    })
 }
 
+/// Reset all counters and set the peak node count to the current node count
+///
+/// This includes the cache statistics (see `manager_cache_stats()`).
 #[no_mangle]
-pub unsafe extern "C" fn bdd_and_not(f: bdd_t, g: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let bdd = f.and_not(g);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_reset_stats(manager: manager_t) {
+    guard((), || {
+        let m = unsafe { &mut *manager._p };
+        m.stats = manager_stats_t {
+            peak_nodes: m.nodes_total,
+            ..Default::default()
+        };
+        m.cache.hits = 0;
+        m.cache.misses = 0;
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_ite(f: bdd_t, g: bdd_t, h: bdd_t) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let h = unsafe { &*h._p };
-    let bdd = Bdd::if_then_else(f, g, h);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_ithvar(manager: manager_t, i: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let bdd = unsafe { &*(manager._p) }.mk_var(BddVariable::from_index(i as usize));
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_var_exists(f: bdd_t, var: u16) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let bdd = f.var_exists(BddVariable::from_index(var as usize));
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_nithvar(manager: manager_t, i: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let bdd = unsafe { &*(manager._p) }.mk_not_var(BddVariable::from_index(i as usize));
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_var_forall(f: bdd_t, var: u16) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let bdd = f.var_for_all(BddVariable::from_index(var as usize));
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_true(manager: manager_t) -> bdd_t {
+    guard(bdd_t::null(), || unsafe {
+        Manager::constant(manager._p, true)
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_exists(f: bdd_t, vars: *const u16, num_vars: usize) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
-        .collect();
-    let bdd = f.exists(&vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_false(manager: manager_t) -> bdd_t {
+    guard(bdd_t::null(), || unsafe {
+        Manager::constant(manager._p, false)
+    })
 }
 
+/// Conjunction of the literals given by an assignment
+///
+/// `values` must point to `num` values, where `num` is the manager's number of
+/// variables. Variable `i` occurs positively if `values[i]` is 1, negatively if
+/// it is 0, and not at all if it is -1. This is the inverse of
+/// `bdd_pickcube()`. Returns a null handle if `num` does not match or a value
+/// is invalid.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_forall(f: bdd_t, vars: *const u16, num_vars: usize) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
-        .collect();
-    let bdd = f.for_all(&vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+pub unsafe extern "C" fn manager_cube(manager: manager_t, values: *const i8, num: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        if num != m.num_vars() as usize {
+            return bdd_t::error(format!(
+                "Assignment length {num} does not match the variable count {}",
+                m.num_vars()
+            ));
+        }
+        let Some(values) = (unsafe { read_assignment(values, num) }) else {
+            return bdd_t::error("Assignment values must be 0, 1, or -1");
+        };
+        let bdd = m.mk_conjunctive_clause(&partial_valuation(&values));
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
+/// Construct a BDD from an explicit node table
+///
+/// Node IDs 0 and 1 denote the false and true terminal, respectively, and ID
+/// `i + 2` denotes the decision node on variable `vars[i]` with children
+/// `lows[i]` and `highs[i]`. `vars`, `lows`, and `highs` must point to
+/// `num_nodes` values each, and `root` is the ID of the root node. This is the
+/// format of `bdd_to_json()`. Returns a null handle if an ID or variable is out
+/// of range, a node's variable is not less than its children's variables, a
+/// node has equal children, or two nodes are equal (including unreachable
+/// nodes).
 #[no_mangle]
-pub unsafe extern "C" fn bdd_and_exists(
-    f: bdd_t,
-    g: bdd_t,
+pub unsafe extern "C" fn manager_from_nodes(
+    manager: manager_t,
     vars: *const u16,
-    num_vars: usize,
+    lows: *const u32,
+    highs: *const u32,
+    num_nodes: usize,
+    root: u32,
 ) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
-        .collect();
-    let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::and, &vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let vars = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_nodes) };
+        let lows = unsafe { &*std::ptr::slice_from_raw_parts(lows, num_nodes) };
+        let highs = unsafe { &*std::ptr::slice_from_raw_parts(highs, num_nodes) };
+        let num_ids = num_nodes as u64 + 2;
+        if root as u64 >= num_ids {
+            return bdd_t::error(format!("Root {root} out of range"));
+        }
+        // Variable of the node with the given ID, `num_vars` for terminals
+        let var_of = |id: u32| match id {
+            0 | 1 => m.num_vars(),
+            _ => vars[id as usize - 2],
+        };
+        let mut unique = HashMap::with_capacity(num_nodes);
+        for i in 0..num_nodes {
+            let (var, low, high) = (vars[i], lows[i], highs[i]);
+            let id = i + 2;
+            if var >= m.num_vars() {
+                return bdd_t::error(format!("Node {id}: variable {var} out of range"));
+            }
+            if low as u64 >= num_ids || high as u64 >= num_ids {
+                return bdd_t::error(format!("Node {id}: child out of range"));
+            }
+            if var >= var_of(low) || var >= var_of(high) {
+                return bdd_t::error(format!("Node {id}: children must have greater variables"));
+            }
+            if low == high {
+                return bdd_t::error(format!("Node {id}: redundant node with equal children"));
+            }
+            if let Some(other) = unique.insert((var, low, high), id) {
+                return bdd_t::error(format!("Nodes {other} and {id} are equal"));
+            }
+        }
+
+        // Children have greater variables, so they are constructed first
+        let mut order: Vec<usize> = (0..num_nodes).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(vars[i]));
+        let mut ptrs = vec![BddPointer::zero(); num_nodes + 2];
+        ptrs[1] = BddPointer::one();
+        let mut builder = BddBuilder::new(m.num_vars());
+        for i in order {
+            ptrs[i + 2] = builder.mk_node(
+                BddVariable::from_index(vars[i] as usize),
+                ptrs[lows[i] as usize],
+                ptrs[highs[i] as usize],
+            );
+        }
+        let bdd = builder.build(ptrs[root as usize]);
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
+/// Construct a BDD from its truth table
+///
+/// `bits` must point to `num_bytes` bytes holding the `2^num_vars` output bits,
+/// where `num_vars` must be the manager's number of variables and `num_bytes`
+/// must be `ceil(2^num_vars / 8)`. Bit `r % 8` (starting from the least
+/// significant one) of `bits[r / 8]` is the output for row `r`. In row `r`,
+/// variable `i` has the value of bit `i` of `r`, i.e., the first variable
+/// corresponds to the least significant bit. Returns a null handle if the
+/// arguments are inconsistent or the table has more rows than the manager's
+/// node limit.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_or_exists(
-    f: bdd_t,
-    g: bdd_t,
-    vars: *const u16,
-    num_vars: usize,
+pub unsafe extern "C" fn manager_from_truth_table(
+    manager: manager_t,
+    bits: *const u8,
+    num_bytes: usize,
+    num_vars: u16,
 ) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        if num_vars != m.num_vars() {
+            return bdd_t::error(format!(
+                "Truth table has {num_vars} variables, but the manager has {}",
+                m.num_vars()
+            ));
+        }
+
+        // The bottom level of the construction has a node per row
+        let num_rows = match 1usize.checked_shl(num_vars as u32) {
+            Some(num_rows) if num_rows <= m.max_nodes_total => num_rows,
+            _ => {
+                return bdd_t::error(format!(
+                    "Truth table with {num_vars} variables exceeds the node limit"
+                ))
+            }
+        };
+        if num_bytes != num_rows.div_ceil(8) {
+            return bdd_t::error(format!(
+                "Truth table with {num_vars} variables needs {} bytes, got {num_bytes}",
+                num_rows.div_ceil(8)
+            ));
+        }
+        let bits = unsafe { &*std::ptr::slice_from_raw_parts(bits, num_bytes) };
+
+        let mut builder = BddBuilder::new(num_vars);
+        let mut level: Vec<BddPointer> = (0..num_rows)
+            .map(|r| BddPointer::from_bool(bits[r / 8] >> (r % 8) & 1 != 0))
+            .collect();
+        // Eliminate the variables bottom-up, i.e., starting with the most
+        // significant bit of the row index
+        for var in (0..num_vars).rev() {
+            let half = level.len() / 2;
+            let var = BddVariable::from_index(var as usize);
+            level = (0..half)
+                .map(|r| builder.mk_node(var, level[r], level[r + half]))
+                .collect();
+        }
+        unsafe { bdd_t::from_bdd(builder.build(level[0]), manager._p) }
+    })
+}
+
+/// Read `num` distinct variable indices of `manager` and sort them
+unsafe fn read_distinct_vars(
+    manager: &Manager,
+    vars: *const u16,
+    num: usize,
+) -> Result<Vec<BddVariable>, String> {
+    let num_vars = manager.num_vars();
+    let mut res = Vec::with_capacity(num);
+    for &v in unsafe { &*std::ptr::slice_from_raw_parts(vars, num) } {
+        if v >= num_vars {
+            return Err(format!(
+                "Variable {v} is out of range (the manager has {num_vars} variables)"
+            ));
+        }
+        res.push(BddVariable::from_index(v as usize));
+    }
+    res.sort();
+    if let Some(w) = res.windows(2).find(|w| w[0] == w[1]) {
+        return Err(format!("Variable {} occurs twice", w[0].to_index()));
+    }
+    Ok(res)
+}
+
+/// Function that is true iff between `lo` and `hi` (inclusive) of the sorted
+/// variables `vars` are true
+///
+/// The BDD is built bottom-up with one node per variable and number of true
+/// variables above it. Counts beyond the point where the outcome is decided are
+/// merged, so there are `O(num * lo)` nodes if `hi >= num` and `O(num * hi)`
+/// nodes otherwise.
+fn cardinality(num_vars: u16, vars: &[BddVariable], lo: usize, hi: usize) -> Bdd {
+    let n = vars.len();
+    // With `hi >= n`, any count of at least `lo` is accepted, otherwise counts
+    // above `hi` are rejected.
+    let cap = if hi >= n { lo } else { hi + 1 };
+    let mut builder = BddBuilder::new(num_vars);
+    let mut level: Vec<BddPointer> = (0..=cap)
+        .map(|count| BddPointer::from_bool(lo <= count && count <= hi))
         .collect();
-    let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::or, &vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    for &var in vars.iter().rev() {
+        level = (0..=cap)
+            .map(|count| builder.mk_node(var, level[count], level[(count + 1).min(cap)]))
+            .collect();
+    }
+    builder.build(level[0])
 }
 
+/// Function that is true iff at least `k` of the `num` variables `vars` are true
+///
+/// The variables must be distinct, and `k` must not exceed `num`. The BDD has
+/// `O(num * k)` nodes. Returns a null handle if the input is invalid.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_and_forall(
-    f: bdd_t,
-    g: bdd_t,
+pub unsafe extern "C" fn manager_at_least_k(
+    manager: manager_t,
     vars: *const u16,
-    num_vars: usize,
+    num: usize,
+    k: u32,
 ) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
-        .collect();
-    let bdd = Bdd::binary_op_with_for_all(f, g, biodivine_lib_bdd::op_function::and, &vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let vars = match unsafe { read_distinct_vars(m, vars, num) } {
+            Ok(vars) => vars,
+            Err(err) => return bdd_t::error(err),
+        };
+        if k as usize > num {
+            return bdd_t::error(format!(
+                "Threshold {k} exceeds the number of variables {num}"
+            ));
+        }
+        let bdd = cardinality(m.num_vars(), &vars, k as usize, num);
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
+/// Function that is true iff at most `k` of the `num` variables `vars` are true
+///
+/// The same conditions as for `manager_at_least_k()` apply.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_or_forall(
-    f: bdd_t,
-    g: bdd_t,
+pub unsafe extern "C" fn manager_at_most_k(
+    manager: manager_t,
     vars: *const u16,
-    num_vars: usize,
+    num: usize,
+    k: u32,
 ) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let g = unsafe { &*g._p };
-    let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
-        .iter()
-        .map(|&v| BddVariable::from_index(v as usize))
-        .collect();
-    let bdd = Bdd::binary_op_with_for_all(f, g, biodivine_lib_bdd::op_function::or, &vars);
-    unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let vars = match unsafe { read_distinct_vars(m, vars, num) } {
+            Ok(vars) => vars,
+            Err(err) => return bdd_t::error(err),
+        };
+        if k as usize > num {
+            return bdd_t::error(format!(
+                "Threshold {k} exceeds the number of variables {num}"
+            ));
+        }
+        let bdd = cardinality(m.num_vars(), &vars, 0, k as usize);
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
+/// Function that is true iff exactly `k` of the `num` variables `vars` are true
+///
+/// The same conditions as for `manager_at_least_k()` apply. In particular, for
+/// `num == 0` and `k == 0`, the result is true.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_rename_variable(f: bdd_t, x: u16, y: u16) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let mut g = f.bdd.clone();
-    unsafe {
-        g.rename_variable(
-            BddVariable::from_index(x as usize),
-            BddVariable::from_index(y as usize),
-        )
-    };
-    unsafe { bdd_t::from_bdd(g, f.manager) }
+pub unsafe extern "C" fn manager_exactly_k(
+    manager: manager_t,
+    vars: *const u16,
+    num: usize,
+    k: u32,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let vars = match unsafe { read_distinct_vars(m, vars, num) } {
+            Ok(vars) => vars,
+            Err(err) => return bdd_t::error(err),
+        };
+        if k as usize > num {
+            return bdd_t::error(format!(
+                "Threshold {k} exceeds the number of variables {num}"
+            ));
+        }
+        let bdd = cardinality(m.num_vars(), &vars, k as usize, k as usize);
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
+/// Function that is true iff at least `lo` and at most `hi` of the `num`
+/// variables `vars` are true
+///
+/// The variables must be distinct, and `lo <= hi <= num` must hold. Returns a
+/// null handle if the input is invalid.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_rename_variables(
-    f: bdd_t,
-    var_pairs: *const VarPair,
-    num_pairs: usize,
+pub unsafe extern "C" fn manager_between(
+    manager: manager_t,
+    vars: *const u16,
+    num: usize,
+    lo: u32,
+    hi: u32,
 ) -> bdd_t {
-    let f = unsafe { &*f._p };
-    let var_map: HashMap<BddVariable, BddVariable> =
-        unsafe { &*std::ptr::slice_from_raw_parts(var_pairs, num_pairs) }
-            .iter()
-            .map(|p| {
-                (
-                    BddVariable::from_index(p.first as usize),
-                    BddVariable::from_index(p.second as usize),
-                )
-            })
-            .collect();
-    let mut g = f.bdd.clone();
-    unsafe { g.rename_variables(&var_map) };
-    unsafe { bdd_t::from_bdd(g, f.manager) }
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let vars = match unsafe { read_distinct_vars(m, vars, num) } {
+            Ok(vars) => vars,
+            Err(err) => return bdd_t::error(err),
+        };
+        if lo > hi || hi as usize > num {
+            return bdd_t::error(format!(
+                "Bounds {lo}..={hi} are invalid for {num} variables"
+            ));
+        }
+        let bdd = cardinality(m.num_vars(), &vars, lo as usize, hi as usize);
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_nodecount(f: bdd_t) -> usize {
-    unsafe { &*f._p }.size()
+pub unsafe extern "C" fn bdd_ref(f: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let bdd = unsafe { &mut *f._p };
+        if bdd.cached && bdd.rc == 1 {
+            unsafe { &mut *bdd.manager }.rc += 1;
+        }
+        bdd.rc += 1;
+        f
+    })
+}
+#[no_mangle]
+pub unsafe extern "C" fn bdd_unref(f: bdd_t) {
+    guard((), || {
+        if f._p.is_null() {
+            return;
+        }
+        let bdd = unsafe { &mut *f._p };
+        if bdd.rc == 1 {
+            unsafe { &mut *bdd.manager }.nodes_total -= bdd.size();
+            unsafe { manager_unref(manager_t { _p: bdd.manager }) };
+            drop(unsafe { Box::from_raw(f._p) });
+        } else {
+            bdd.rc -= 1;
+            if bdd.cached && bdd.rc == 1 {
+                unsafe { manager_unref(manager_t { _p: bdd.manager }) };
+            }
+        }
+    })
 }
 
+/// Move `f` to `manager`
+///
+/// Variables are matched by name, so for managers created using
+/// `manager_new()`, `manager_extend()`, or `manager_clone()`, the variable
+/// indices stay the same. Returns a null handle if `f` depends on a variable
+/// `manager` does not have.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_satcount(f: bdd_t) -> f64 {
-    unsafe { &*f._p }.cardinality()
+pub unsafe extern "C" fn bdd_transfer(f: bdd_t, manager: manager_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        let from = unsafe { &*f.manager };
+        match unsafe { &*manager._p }.transfer_from(f, from) {
+            Some(bdd) => unsafe { bdd_t::from_bdd(bdd, manager._p) },
+            None => bdd_t::error("The target manager lacks variables or their order differs"),
+        }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_eq(f: bdd_t, g: bdd_t) -> bool {
-    let f = unsafe { &**f._p };
-    let g = unsafe { &**g._p };
-    f == g
+pub unsafe extern "C" fn bdd_not(f: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.not += 1;
+        let bdd = f.not();
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
 }
 
+/// Replace `*f` by its negation
+///
+/// This consumes the reference `*f` and stores a new reference to the negation
+/// in `*f`. If `*f` is the only reference to its BDD, the BDD is negated in
+/// place and the handle stays the same. Otherwise, this is equivalent to
+/// `g = bdd_not(*f); bdd_unref(*f); *f = g;`, leaving the other references
+/// untouched. Note that copies of a handle that were not obtained via
+/// `bdd_ref()` observe the in-place negation.
 #[no_mangle]
-pub unsafe extern "C" fn bdd_pickcube(f: bdd_t) -> bdd_assignment_t {
-    let f = unsafe { &**f._p };
-    if f.is_false() {
-        return bdd_assignment_t {
-            data: std::ptr::null_mut(),
-            len: 0,
+pub unsafe extern "C" fn bdd_not_inplace(f: *mut bdd_t) {
+    guard((), || {
+        let handle = unsafe { *f };
+        let rc_bdd = unsafe { &mut *handle._p };
+        if rc_bdd.rc == 1 && !rc_bdd.cached {
+            let old_size = rc_bdd.size();
+            rc_bdd.bdd = rc_bdd.not();
+            let m = unsafe { &mut *rc_bdd.manager };
+            m.stats.not += 1;
+            m.nodes_total -= old_size;
+            m.add_nodes(rc_bdd.size());
+        } else {
+            unsafe {
+                *f = bdd_not(handle);
+                bdd_unref(handle);
+            }
+        }
+    })
+}
+
+/// Set the number of entries of the manager's operation cache
+///
+/// The cache memoizes the results of `bdd_and()`, `bdd_or()`, `bdd_xor()`,
+/// `bdd_imp()`, `bdd_iff()`, and `bdd_and_not()` across calls. Each result has
+/// a single slot determined by the operator and the operands, and a new result
+/// replaces the one in its slot. A lookup hashes and compares the operands,
+/// which takes time linear in their size. The cache holds copies of the
+/// operands and results, which are not included in `manager_node_count()` and
+/// do not count towards the node limit. Changing the size clears the cache, and
+/// a size of 0 (the default) disables it.
+#[no_mangle]
+pub unsafe extern "C" fn manager_set_cache_size(manager: manager_t, entries: usize) {
+    guard((), || {
+        let cache = &mut unsafe { &mut *manager._p }.cache;
+        cache.capacity = entries;
+        cache.slots = Vec::new();
+    })
+}
+
+/// Write the number of operation cache hits and misses to `*hits` and
+/// `*misses`
+///
+/// Calls made while the cache is disabled are not counted. Both counters are
+/// reset by `manager_reset_stats()`.
+#[no_mangle]
+pub unsafe extern "C" fn manager_cache_stats(manager: manager_t, hits: *mut u64, misses: *mut u64) {
+    guard((), || {
+        let cache = unsafe { &(*manager._p).cache };
+        unsafe {
+            *hits = cache.hits;
+            *misses = cache.misses;
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::And, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_or(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.or += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::Or, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_xor(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.xor += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::Xor, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Exclusive disjunction of `f` and the cube given by `values`
+///
+/// `values` must point to `num` values, where `num` is the number of variables
+/// of `f`. They describe the cube as in `manager_cube()`. Returns a null handle
+/// if `num` does not match or a value is invalid.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_xor_cube(f: bdd_t, values: *const i8, num: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        if num != f.num_vars() as usize {
+            return bdd_t::error(format!(
+                "Assignment length {num} does not match the variable count {}",
+                f.num_vars()
+            ));
+        }
+        let Some(values) = (unsafe { read_assignment(values, num) }) else {
+            return bdd_t::error("Assignment values must be 0, 1, or -1");
+        };
+        unsafe { &mut *f.manager }.stats.xor += 1;
+        let literals = partial_valuation(&values).to_values();
+        let bdd = f.xor(&cube(f.num_vars(), &literals));
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_imp(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.imp += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::Imp, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_iff(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.iff += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::Iff, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and_not(f: bdd_t, g: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and_not += 1;
+        let g = unsafe { &*g._p };
+        let bdd = unsafe { &mut *f.manager }.cache.apply(BinOp::AndNot, f, g);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Set difference of `f` and `g`, i.e., the assignments satisfying `f` but not
+/// `g`
+///
+/// This is the same as `bdd_and_not(f, g)` and also counted as such.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_setminus(f: bdd_t, g: bdd_t) -> bdd_t {
+    unsafe { bdd_and_not(f, g) }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_ite(f: bdd_t, g: bdd_t, h: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g, h]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.ite += 1;
+        let g = unsafe { &*g._p };
+        let h = unsafe { &*h._p };
+        let bdd = Bdd::if_then_else(f, g, h);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Opcode for `bdd_eval_tree()`: push the next operand
+pub const BDD_OP_PUSH: u8 = 0;
+/// Opcode for `bdd_eval_tree()`: replace the top of the stack by its negation
+pub const BDD_OP_NOT: u8 = 1;
+/// Opcode for `bdd_eval_tree()`: replace the top two elements by their
+/// conjunction
+pub const BDD_OP_AND: u8 = 2;
+/// Opcode for `bdd_eval_tree()`: replace the top two elements by their
+/// disjunction
+pub const BDD_OP_OR: u8 = 3;
+/// Opcode for `bdd_eval_tree()`: replace the top two elements by their
+/// exclusive disjunction
+pub const BDD_OP_XOR: u8 = 4;
+
+/// Evaluate an expression given in postfix notation
+///
+/// `ops` must point to `num_ops` opcodes (`BDD_OP_*`) and `args` to `num_args`
+/// operands, which must belong to the same manager. The opcodes are executed on
+/// an initially empty stack, where `BDD_OP_PUSH` pushes the next operand. For
+/// example, `x ∧ ¬y` is encoded as `PUSH PUSH NOT AND` with `args = [x, y]`.
+/// Intermediate results are freed as soon as they are consumed. Returns a null
+/// handle if an opcode is invalid, an opcode lacks operands, not all operands
+/// are used, or the stack does not end up with exactly one element.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_eval_tree(
+    ops: *const u8,
+    args: *const bdd_t,
+    num_ops: usize,
+    num_args: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        use std::borrow::Cow;
+
+        let ops = unsafe { &*std::ptr::slice_from_raw_parts(ops, num_ops) };
+        let args = unsafe { &*std::ptr::slice_from_raw_parts(args, num_args) };
+        if !unsafe { same_manager(args) } {
+            return bdd_t::null();
+        }
+        let Some(first) = args.first() else {
+            return bdd_t::error("Expression without operands");
         };
+        let manager = unsafe { &*first._p }.manager;
+        let stats = &mut unsafe { &mut *manager }.stats;
+
+        let mut args = args.iter().map(|f| unsafe { &(*f._p).bdd });
+        let mut stack: Vec<Cow<Bdd>> = Vec::new();
+        for (i, &op) in ops.iter().enumerate() {
+            let arity = match op {
+                BDD_OP_PUSH => 0,
+                BDD_OP_NOT => 1,
+                BDD_OP_AND | BDD_OP_OR | BDD_OP_XOR => 2,
+                _ => return bdd_t::error(format!("Invalid opcode {op} at position {i}")),
+            };
+            if stack.len() < arity {
+                return bdd_t::error(format!("Missing operand for opcode {op} at position {i}"));
+            }
+            let res = match op {
+                BDD_OP_PUSH => match args.next() {
+                    Some(f) => Cow::Borrowed(f),
+                    None => return bdd_t::error(format!("Missing operand at position {i}")),
+                },
+                BDD_OP_NOT => {
+                    stats.not += 1;
+                    Cow::Owned(stack.pop().unwrap().not())
+                }
+                _ => {
+                    let g = stack.pop().unwrap();
+                    let f = stack.pop().unwrap();
+                    Cow::Owned(match op {
+                        BDD_OP_AND => {
+                            stats.and += 1;
+                            f.and(&g)
+                        }
+                        BDD_OP_OR => {
+                            stats.or += 1;
+                            f.or(&g)
+                        }
+                        _ => {
+                            stats.xor += 1;
+                            f.xor(&g)
+                        }
+                    })
+                }
+            };
+            stack.push(res);
+        }
+        if args.next().is_some() {
+            return bdd_t::error("Not all operands were used");
+        }
+        match (stack.pop(), stack.is_empty()) {
+            (Some(res), true) => unsafe { bdd_t::from_bdd(res.into_owned(), manager) },
+            _ => bdd_t::error("Expression does not evaluate to a single BDD"),
+        }
+    })
+}
+
+/// Majority of `a`, `b`, and `c`, i.e., `(a ∧ b) ∨ (b ∧ c) ∨ (a ∧ c)`
+///
+/// All three BDDs must belong to the same manager. Computed as
+/// `ite(a, b ∨ c, b ∧ c)`, so no intermediate result depends on all inputs.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_majority(a: bdd_t, b: bdd_t, c: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[a, b, c]) } {
+            return bdd_t::null();
+        }
+
+        let a = unsafe { &*a._p };
+        unsafe { &mut *a.manager }.stats.ite += 1;
+        let b = unsafe { &*b._p };
+        let c = unsafe { &*c._p };
+        let bdd = Bdd::if_then_else(a, &b.or(c), &b.and(c));
+        unsafe { bdd_t::from_bdd(bdd, a.manager) }
+    })
+}
+
+/// Combine `bdds` using `op` in a balanced binary tree
+fn fold_balanced(mut bdds: Vec<Bdd>, op: impl Fn(&Bdd, &Bdd) -> Bdd) -> Bdd {
+    while bdds.len() > 1 {
+        bdds = bdds
+            .chunks(2)
+            .map(|pair| match pair {
+                [f, g] => op(f, g),
+                [f] => f.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
     }
-    let mut assignment = vec![OptBool::None; f.num_vars() as usize];
-    let mut p = f.root_pointer();
-    while !p.is_one() {
-        let c = f.low_link_of(p);
-        if !c.is_zero() {
-            assignment[f.var_of(p).to_index()] = OptBool::False;
-            p = c;
+    bdds.pop().unwrap()
+}
+
+/// Function that is true iff all of `fs` agree
+///
+/// `fs` must point to `num >= 1` BDDs of the same manager. For a single BDD,
+/// the result is true. Computed as `(f_1 ∧ … ∧ f_n) ∨ ¬(f_1 ∨ … ∨ f_n)`, where
+/// the conjunction and disjunction are built as balanced trees.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_all_equal(fs: *const bdd_t, num: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if num == 0 {
+            return bdd_t::error("bdd_all_equal() requires at least one BDD");
+        }
+        let fs = unsafe { &*std::ptr::slice_from_raw_parts(fs, num) };
+        if !unsafe { same_manager(fs) } {
+            return bdd_t::null();
+        }
+        let bdds: Vec<Bdd> = fs.iter().map(|f| unsafe { &*f._p }.bdd.clone()).collect();
+        let all = fold_balanced(bdds.clone(), Bdd::and);
+        let any = fold_balanced(bdds, Bdd::or);
+        let manager = unsafe { &*fs[0]._p }.manager;
+        unsafe { &mut *manager }.stats.iff += 1;
+        unsafe { bdd_t::from_bdd(all.or(&any.not()), manager) }
+    })
+}
+
+/// Coudert & Madre's restrict operator
+///
+/// `care` is given as a set of nodes in `c` representing their disjunction.
+/// This way, we never need to materialize `c.low ∨ c.high` when abstracting
+/// from a care-set variable that `f` does not depend on.
+fn restrict_rec(
+    f: &Bdd,
+    p: BddPointer,
+    c: &Bdd,
+    mut care: Vec<BddPointer>,
+    builder: &mut BddBuilder,
+    cache: &mut HashMap<(BddPointer, Vec<BddPointer>), BddPointer>,
+) -> BddPointer {
+    care.retain(|q| !q.is_zero());
+    if care.is_empty() {
+        return BddPointer::zero();
+    }
+    if p.is_terminal() {
+        return p;
+    }
+    if care.iter().any(|q| q.is_one()) {
+        care = vec![BddPointer::one()];
+    }
+    care.sort();
+    care.dedup();
+    let key = (p, care);
+    if let Some(&res) = cache.get(&key) {
+        return res;
+    }
+    let care = &key.1;
+
+    let f_var = f.var_of(p);
+    let c_var = care.iter().map(|&q| c.var_of(q)).min().unwrap();
+    let cofactors = |value: bool| -> Vec<BddPointer> {
+        care.iter()
+            .map(|&q| match (c.var_of(q) == c_var, value) {
+                (false, _) => q,
+                (true, false) => c.low_link_of(q),
+                (true, true) => c.high_link_of(q),
+            })
+            .collect()
+    };
+
+    let res = if c_var < f_var {
+        let mut abstracted = cofactors(false);
+        abstracted.append(&mut cofactors(true));
+        restrict_rec(f, p, c, abstracted, builder, cache)
+    } else {
+        let (care_low, care_high) = if c_var == f_var {
+            (cofactors(false), cofactors(true))
         } else {
-            let c = f.high_link_of(p);
-            debug_assert!(!c.is_zero());
-            assignment[f.var_of(p).to_index()] = OptBool::True;
-            p = c;
+            (care.clone(), care.clone())
+        };
+        let (low, high) = (f.low_link_of(p), f.high_link_of(p));
+        if care_low.iter().all(|q| q.is_zero()) {
+            restrict_rec(f, high, c, care_high, builder, cache)
+        } else if care_high.iter().all(|q| q.is_zero()) {
+            restrict_rec(f, low, c, care_low, builder, cache)
+        } else {
+            let low = restrict_rec(f, low, c, care_low, builder, cache);
+            let high = restrict_rec(f, high, c, care_high, builder, cache);
+            builder.mk_node(f_var, low, high)
+        }
+    };
+    cache.insert(key, res);
+    res
+}
+
+/// Simplify `f` assuming that all assignments outside `care` are don't-cares
+///
+/// The result agrees with `f` on every assignment satisfying `care`. It is
+/// computed using the restrict operator, and a new reference to `f` itself is
+/// returned if `care` is true or the restricted BDD would be larger. If `care`
+/// is false, the result is false. `f` and `care` must belong to the same
+/// manager.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_simplify(f: bdd_t, care: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, care]) } {
+            return bdd_t::null();
+        }
+        let bdd = unsafe { &*f._p };
+        unsafe { &mut *bdd.manager }.stats.cofactor += 1;
+        let care = unsafe { &**care._p };
+        if care.is_true() {
+            return unsafe { bdd_ref(f) };
+        }
+        let mut builder = BddBuilder::new(bdd.num_vars());
+        let root = restrict_rec(
+            bdd,
+            bdd.root_pointer(),
+            care,
+            vec![care.root_pointer()],
+            &mut builder,
+            &mut HashMap::new(),
+        );
+        let res = builder.build(root);
+        if res.size() > bdd.size() {
+            return unsafe { bdd_ref(f) };
+        }
+        unsafe { bdd_t::from_bdd(res, bdd.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_var_exists(f: bdd_t, var: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.exists += 1;
+        let bdd = f.var_exists(BddVariable::from_index(var as usize));
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_var_forall(f: bdd_t, var: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.forall += 1;
+        let bdd = f.var_for_all(BddVariable::from_index(var as usize));
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_exists(f: bdd_t, vars: *const u16, num_vars: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.exists += 1;
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = f.exists(&vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_forall(f: bdd_t, vars: *const u16, num_vars: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.forall += 1;
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = f.for_all(&vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Precomputed set of variables for the quantification functions
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct bdd_varset_t {
+    _p: *mut Vec<BddVariable>,
+}
+
+impl bdd_varset_t {
+    fn null() -> Self {
+        bdd_varset_t {
+            _p: std::ptr::null_mut(),
         }
     }
+}
+
+/// Create a variable set from the `num` variable indices `vars`
+///
+/// The set can be used with all BDDs of `manager` and must be freed using
+/// `bdd_varset_free()`. Returns a set with a null pointer if an index is out of
+/// range.
+#[no_mangle]
+pub unsafe extern "C" fn manager_varset(
+    manager: manager_t,
+    vars: *const u16,
+    num: usize,
+) -> bdd_varset_t {
+    guard(bdd_varset_t::null(), || {
+        let num_vars = unsafe { &*manager._p }.num_vars();
+        let vars = unsafe { &*std::ptr::slice_from_raw_parts(vars, num) };
+        if let Some(&v) = vars.iter().find(|&&v| v >= num_vars) {
+            set_error(format!(
+                "Variable {v} is out of range (the manager has {num_vars} variables)"
+            ));
+            return bdd_varset_t::null();
+        }
+        let vars: Vec<BddVariable> = vars
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        bdd_varset_t {
+            _p: Box::into_raw(Box::new(vars)),
+        }
+    })
+}
 
-    assignment.shrink_to_fit();
-    let len = assignment.len();
-    let data = assignment.as_mut_ptr() as *mut i8;
-    std::mem::forget(assignment);
-    bdd_assignment_t { data, len }
+#[no_mangle]
+pub unsafe extern "C" fn bdd_varset_free(set: bdd_varset_t) {
+    guard((), || {
+        if !set._p.is_null() {
+            drop(unsafe { Box::from_raw(set._p) });
+        }
+    })
 }
 
+/// Same as `bdd_exists()`, but with a precomputed variable set
 #[no_mangle]
-pub unsafe extern "C" fn bdd_save(f: bdd_t, path: *const std::ffi::c_char) {
-    let f = unsafe { &**f._p };
-    let f_bytes = f.to_bytes();
+pub unsafe extern "C" fn bdd_exists_set(f: bdd_t, set: bdd_varset_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.exists += 1;
+        let bdd = f.exists(unsafe { &*set._p });
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
 
-    let path_cstr = unsafe { std::ffi::CStr::from_ptr(path) };
-    std::fs::write(Path::new(path_cstr.to_str().unwrap()), f_bytes).unwrap();
+/// Same as `bdd_forall()`, but with a precomputed variable set
+#[no_mangle]
+pub unsafe extern "C" fn bdd_forall_set(f: bdd_t, set: bdd_varset_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.forall += 1;
+        let bdd = f.for_all(unsafe { &*set._p });
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn bdd_load(manager: manager_t, path: *const std::ffi::c_char) -> bdd_t {
-    // Taken from Samuel Pastva and Thomas Henzinger's source code in 'util.rs'
-    // in their artifact: https://zenodo.org/records/7958052
+pub unsafe extern "C" fn bdd_and_exists(
+    f: bdd_t,
+    g: bdd_t,
+    vars: *const u16,
+    num_vars: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and_exists += 1;
+        let g = unsafe { &*g._p };
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::and, &vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_or_exists(
+    f: bdd_t,
+    g: bdd_t,
+    vars: *const u16,
+    num_vars: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.or_exists += 1;
+        let g = unsafe { &*g._p };
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::or, &vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Same as `bdd_and_exists()`, but with a precomputed variable set
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and_exists_set(f: bdd_t, g: bdd_t, set: bdd_varset_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and_exists += 1;
+        let g = unsafe { &*g._p };
+        let vars = unsafe { &*set._p };
+        let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::and, vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Same as `bdd_or_exists()`, but with a precomputed variable set
+#[no_mangle]
+pub unsafe extern "C" fn bdd_or_exists_set(f: bdd_t, g: bdd_t, set: bdd_varset_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.or_exists += 1;
+        let g = unsafe { &*g._p };
+        let vars = unsafe { &*set._p };
+        let bdd = Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::or, vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and_forall(
+    f: bdd_t,
+    g: bdd_t,
+    vars: *const u16,
+    num_vars: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and_forall += 1;
+        let g = unsafe { &*g._p };
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = Bdd::binary_op_with_for_all(f, g, biodivine_lib_bdd::op_function::and, &vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_or_forall(
+    f: bdd_t,
+    g: bdd_t,
+    vars: *const u16,
+    num_vars: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
 
-    let path_cstr = unsafe { std::ffi::CStr::from_ptr(path) };
-    let file = std::fs::File::open(path_cstr.to_str().unwrap()).unwrap();
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.or_forall += 1;
+        let g = unsafe { &*g._p };
+        let vars: Vec<BddVariable> = unsafe { &*std::ptr::slice_from_raw_parts(vars, num_vars) }
+            .iter()
+            .map(|&v| BddVariable::from_index(v as usize))
+            .collect();
+        let bdd = Bdd::binary_op_with_for_all(f, g, biodivine_lib_bdd::op_function::or, &vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
 
-    // Each chunk is 10 bytes large, so we use a multiple of 10. In older
-    // versions of LibBDD, not using a multiple of 10 even caused errors:
-    // https://github.com/sybila/biodivine-lib-bdd/issues/34
-    let mut file = std::io::BufReader::with_capacity(1024 * 10, file);
-    let mut f = Bdd::read_as_bytes(&mut file).unwrap();
+/// Compute `∀ forall_vars. ∃ exist_vars. f ∧ g`
+///
+/// The conjunction and the existential quantification are fused as in
+/// `bdd_and_exists()`, the universal quantification is applied afterwards. The
+/// two variable sets must be disjoint, otherwise a null handle is returned.
+/// `f` and `g` must belong to the same manager.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and_mixed_quant(
+    f: bdd_t,
+    g: bdd_t,
+    exist_vars: *const u16,
+    num_exist_vars: usize,
+    forall_vars: *const u16,
+    num_forall_vars: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_t::null();
+        }
 
-    // Ensure this `Bdd` is compatible with any other loaded using this very
-    // function (i.e., it tracks the same number of variables).
-    unsafe { f.set_num_vars(u16::MAX) };
+        let f = unsafe { &*f._p };
+        let stats = &mut unsafe { &mut *f.manager }.stats;
+        stats.and_exists += 1;
+        stats.forall += 1;
+        let g = unsafe { &*g._p };
+        let to_vars = |vars: *const u16, num: usize| -> Vec<BddVariable> {
+            unsafe { &*std::ptr::slice_from_raw_parts(vars, num) }
+                .iter()
+                .map(|&v| BddVariable::from_index(v as usize))
+                .collect()
+        };
+        let exist_vars = to_vars(exist_vars, num_exist_vars);
+        let forall_vars = to_vars(forall_vars, num_forall_vars);
+        if let Some(var) = exist_vars.iter().find(|v| forall_vars.contains(v)) {
+            return bdd_t::error(format!(
+                "Variable {} is quantified both existentially and universally",
+                var.to_index()
+            ));
+        }
+        let bdd =
+            Bdd::binary_op_with_exists(f, g, biodivine_lib_bdd::op_function::and, &exist_vars)
+                .for_all(&forall_vars);
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
 
-    unsafe { bdd_t::from_bdd(f, manager._p) }
+#[no_mangle]
+pub unsafe extern "C" fn bdd_rename_variable(f: bdd_t, x: u16, y: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        let mut g = f.bdd.clone();
+        unsafe {
+            g.rename_variable(
+                BddVariable::from_index(x as usize),
+                BddVariable::from_index(y as usize),
+            )
+        };
+        unsafe { bdd_t::from_bdd(g, f.manager) }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_rename_variables(
+    f: bdd_t,
+    var_pairs: *const VarPair,
+    num_pairs: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        let var_map: HashMap<BddVariable, BddVariable> =
+            unsafe { &*std::ptr::slice_from_raw_parts(var_pairs, num_pairs) }
+                .iter()
+                .map(|p| {
+                    (
+                        BddVariable::from_index(p.first as usize),
+                        BddVariable::from_index(p.second as usize),
+                    )
+                })
+                .collect();
+        let mut g = f.bdd.clone();
+        unsafe { g.rename_variables(&var_map) };
+        unsafe { bdd_t::from_bdd(g, f.manager) }
+    })
+}
+
+/// Like `bdd_rename_variables()`, but validate the renaming first
+///
+/// Each variable may be renamed at most once, the new variables must be in the
+/// manager's range, and the renaming must preserve the order of the variables
+/// in the support of `f` (variables not mentioned in `var_pairs` keep their
+/// index). Returns a null handle if any of these conditions is violated.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_rename_variables_checked(
+    f: bdd_t,
+    var_pairs: *const VarPair,
+    num_pairs: usize,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        let num_vars = unsafe { &*f.manager }.num_vars();
+        let mut var_map: HashMap<BddVariable, BddVariable> = HashMap::new();
+        for p in unsafe { &*std::ptr::slice_from_raw_parts(var_pairs, num_pairs) } {
+            if p.second >= num_vars {
+                return bdd_t::error(format!(
+                    "Variable {} is out of range 0..{num_vars}",
+                    p.second
+                ));
+            }
+            let (x, y) = (
+                BddVariable::from_index(p.first as usize),
+                BddVariable::from_index(p.second as usize),
+            );
+            if var_map.insert(x, y).is_some() {
+                return bdd_t::error(format!("Variable {} is renamed twice", p.first));
+            }
+        }
+
+        let mut support: Vec<BddVariable> = f.support_set().into_iter().collect();
+        support.sort();
+        let map = |var| *var_map.get(&var).unwrap_or(&var);
+        if let Some(w) = support.windows(2).find(|w| map(w[0]) >= map(w[1])) {
+            return bdd_t::error(format!(
+                "Renaming {} to {} and {} to {} does not preserve the variable order",
+                w[0].to_index(),
+                map(w[0]).to_index(),
+                w[1].to_index(),
+                map(w[1]).to_index()
+            ));
+        }
+
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        let mut g = f.bdd.clone();
+        unsafe { g.rename_variables(&var_map) };
+        unsafe { bdd_t::from_bdd(g, f.manager) }
+    })
+}
+
+/// Restrict `f` to a partial assignment and check whether the result is constant
+///
+/// `values` must point to `num` values (0, 1, or -1), where `num` is the
+/// number of variables of `f`. All variables `i` with `values[i] != -1` are
+/// fixed accordingly. The resulting BDD is written to `*out`. Returns 0 or 1
+/// if it is constant false or true, respectively, and -1 otherwise. If `num`
+/// does not match or `values` contains an invalid value, `*out` is set to a
+/// null handle and -2 is returned.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_eval_partial(
+    f: bdd_t,
+    values: *const i8,
+    num: usize,
+    out: *mut bdd_t,
+) -> i8 {
+    guard(-2, || {
+        let f = unsafe { &*f._p };
+        let values = match unsafe { read_assignment(values, num) } {
+            Some(values) if num == f.num_vars() as usize => values,
+            Some(_) => {
+                unsafe {
+                    *out = bdd_t::error("Assignment length does not match the variable count")
+                };
+                return -2;
+            }
+            None => {
+                unsafe { *out = bdd_t::error("Assignment values must be 0, 1, or -1") };
+                return -2;
+            }
+        };
+        unsafe { &mut *f.manager }.stats.cofactor += 1;
+        let bdd = f.restrict(&partial_valuation(&values).to_values());
+        let res = if bdd.is_false() {
+            0
+        } else if bdd.is_true() {
+            1
+        } else {
+            -1
+        };
+        unsafe { *out = bdd_t::from_bdd(bdd, f.manager) };
+        res
+    })
+}
+
+/// Cofactor of `f` with `var` fixed to `value`, checking whether it is constant
+///
+/// The cofactor is written to `*out`. Returns 0 or 1 if it is constant false
+/// or true, respectively, -1 otherwise, and -2 on an internal error.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_cofactor_const(
+    f: bdd_t,
+    var: u16,
+    value: bool,
+    out: *mut bdd_t,
+) -> i8 {
+    guard(-2, || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.cofactor += 1;
+        let bdd = f.var_restrict(BddVariable::from_index(var as usize), value);
+        let res = if bdd.is_false() {
+            0
+        } else if bdd.is_true() {
+            1
+        } else {
+            -1
+        };
+        unsafe { *out = bdd_t::from_bdd(bdd, f.manager) };
+        res
+    })
+}
+
+/// Replace each variable `i` in `f` by `i + offset`
+///
+/// Returns a null handle if a variable in the support of `f` would be moved out
+/// of the manager's variable range.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_shift_vars(f: bdd_t, offset: i32) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        let num_vars = unsafe { &*f.manager }.num_vars() as i64;
+        let mut var_map: HashMap<BddVariable, BddVariable> = HashMap::new();
+        for var in f.support_set() {
+            let new = var.to_index() as i64 + offset as i64;
+            if !(0..num_vars).contains(&new) {
+                return bdd_t::error(format!(
+                    "Shifting variable {} by {offset} leaves the range 0..{num_vars}",
+                    var.to_index()
+                ));
+            }
+            var_map.insert(var, BddVariable::from_index(new as usize));
+        }
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        let mut g = f.bdd.clone();
+        // Shifting preserves the variable order
+        unsafe { g.rename_variables(&var_map) };
+        unsafe { bdd_t::from_bdd(g, f.manager) }
+    })
+}
+
+/// Swap the roles of variables `x` and `y` in `f`
+///
+/// In contrast to `bdd_rename_variables()`, this works for arbitrary `x` and
+/// `y`, even if the renaming does not preserve the variable order. Swapping a
+/// variable with itself returns (a copy of) `f`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_swap_vars(f: bdd_t, x: u16, y: u16) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        if x == y {
+            return unsafe { bdd_t::from_bdd(f.bdd.clone(), f.manager) };
+        }
+        let x = BddVariable::from_index(x as usize);
+        let y = BddVariable::from_index(y as usize);
+        let cofactor = |vx, vy| f.restrict(&[(x, vx), (y, vy)]);
+        let x = literal(f.num_vars(), x, true);
+        let y = literal(f.num_vars(), y, true);
+        // Where `x` is 1 and `y` is 0 in the result, `f` has `x` = 0 and `y` = 1.
+        let bdd = Bdd::if_then_else(
+            &x,
+            &Bdd::if_then_else(&y, &cofactor(true, true), &cofactor(false, true)),
+            &Bdd::if_then_else(&y, &cofactor(true, false), &cofactor(false, false)),
+        );
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Apply the variable permutation `perm` to `f`
+///
+/// `perm` must point to `num` values such that variable `i` is replaced by
+/// variable `perm[i]`. The values must be a permutation of `0..num`, and `num`
+/// must not exceed the number of variables of `f`. Variables from `num` onward
+/// are left unchanged. Returns a null handle if `perm` is invalid. See
+/// `bdd_to_order()` for specifying the new order of the variables instead.
+///
+/// Order-preserving permutations are implemented by relabeling the nodes, all
+/// others require rebuilding the BDD.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_permute(f: bdd_t, perm: *const u16, num: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        if num > f.num_vars() as usize {
+            return bdd_t::error(format!(
+                "Permutation of {num} variables, but there are only {}",
+                f.num_vars()
+            ));
+        }
+        let perm = unsafe { &*std::ptr::slice_from_raw_parts(perm, num) };
+        let mut seen = vec![false; num];
+        for &v in perm {
+            match seen.get_mut(v as usize) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return bdd_t::error(format!("Not a permutation of 0..{num}")),
+            }
+        }
+        unsafe { &mut *f.manager }.stats.rename += 1;
+        let map = |var: BddVariable| match perm.get(var.to_index()) {
+            Some(&v) => BddVariable::from_index(v as usize),
+            None => var,
+        };
+
+        let mut support: Vec<BddVariable> = f.support_set().into_iter().collect();
+        support.sort();
+        let bdd = if support.windows(2).all(|w| map(w[0]) < map(w[1])) {
+            let var_map: HashMap<BddVariable, BddVariable> =
+                support.iter().map(|&v| (v, map(v))).collect();
+            let mut g = f.bdd.clone();
+            unsafe { g.rename_variables(&var_map) };
+            g
+        } else {
+            // Parents are stored after their children
+            let mut results: Vec<Bdd> = Vec::with_capacity(f.size());
+            for p in (0..f.size()).map(BddPointer::from_index) {
+                let res = if p.is_terminal() {
+                    BddBuilder::new(f.num_vars()).build(p)
+                } else {
+                    Bdd::if_then_else(
+                        &literal(f.num_vars(), map(f.var_of(p)), true),
+                        &results[f.high_link_of(p).to_index()],
+                        &results[f.low_link_of(p).to_index()],
+                    )
+                };
+                results.push(res);
+            }
+            results.swap_remove(f.root_pointer().to_index())
+        };
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Relabel the variables of `f` such that they appear in the order `order`
+///
+/// `order` must point to `num` values listing the variables in their new order,
+/// i.e., variable `order[j]` is replaced by variable `j`. This is the inverse of
+/// `bdd_permute()`, where `perm[i]` is the new index of variable `i`. The same
+/// conditions as for `bdd_permute()` apply.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_order(f: bdd_t, order: *const u16, num: usize) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let order = unsafe { &*std::ptr::slice_from_raw_parts(order, num) };
+        let mut perm: Vec<Option<u16>> = vec![None; num];
+        for (j, &v) in order.iter().enumerate() {
+            match perm.get_mut(v as usize) {
+                Some(p @ None) => *p = Some(j as u16),
+                _ => return bdd_t::error(format!("Not a permutation of 0..{num}")),
+            }
+        }
+        let perm: Vec<u16> = perm.into_iter().map(Option::unwrap).collect();
+        unsafe { bdd_permute(f, perm.as_ptr(), num) }
+    })
+}
+
+/// Mark the variables in the supports of `f` and `g` with 1, all others with -1
+///
+/// With `shared`, only variables in both supports are marked.
+fn support_assignment(f: &Bdd, g: &Bdd, shared: bool) -> bdd_assignment_t {
+    let num_vars = f.num_vars().max(g.num_vars()) as usize;
+    let mut in_f = vec![false; num_vars];
+    for var in f.support_set() {
+        in_f[var.to_index()] = true;
+    }
+    let mut in_g = vec![false; num_vars];
+    for var in g.support_set() {
+        in_g[var.to_index()] = true;
+    }
+    let assignment = (0..num_vars)
+        .map(|i| {
+            let member = if shared {
+                in_f[i] && in_g[i]
+            } else {
+                in_f[i] || in_g[i]
+            };
+            if member {
+                OptBool::True
+            } else {
+                OptBool::None
+            }
+        })
+        .collect();
+    bdd_assignment_t::from_vec(assignment)
+}
+
+/// Variables in the supports of both `f` and `g`
+///
+/// The result has an entry per variable, which is 1 for the shared variables
+/// and -1 for all others. `f` and `g` must belong to the same manager,
+/// otherwise the result has a null `data` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_support_shared(f: bdd_t, g: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_assignment_t::null();
+        }
+        support_assignment(unsafe { &*f._p }, unsafe { &*g._p }, true)
+    })
+}
+
+/// Variables in the support of `f` or `g`
+///
+/// Like `bdd_support_shared()`, but for the union of the supports.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_support_combined(f: bdd_t, g: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return bdd_assignment_t::null();
+        }
+        support_assignment(unsafe { &*f._p }, unsafe { &*g._p }, false)
+    })
+}
+
+/// Whether `f` is a single cube, i.e., a conjunction of literals
+///
+/// This is the case iff `f` has exactly one path to the true terminal, and
+/// each decision node on it has the false terminal as other child. The true
+/// BDD is the empty conjunction and hence a cube, the false BDD is not.
+/// Unlike a single valuation, a cube need not fix all variables, so `x0` is a
+/// cube even if the manager has further variables.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_is_single_cube(f: bdd_t) -> bool {
+    guard(false, || unsafe { &*f._p }.is_clause())
+}
+
+/// Whether `f` is a single literal, i.e., a variable or its negation
+///
+/// If so, the variable is written to `*out_var` and its phase (true for the
+/// positive literal) to `*out_phase`. Otherwise, the outputs are left
+/// untouched.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_as_literal(f: bdd_t, out_var: *mut u16, out_phase: *mut bool) -> bool {
+    guard(false, || {
+        let f = unsafe { &*f._p };
+        if f.size() != 3 {
+            return false;
+        }
+        let root = f.root_pointer();
+        unsafe {
+            *out_var = f.var_of(root).to_index() as u16;
+            *out_phase = f.high_link_of(root).is_one();
+        }
+        true
+    })
+}
+
+/// Whether `var` is in the support of `f`
+///
+/// Constant BDDs do not depend on any variable.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_depends_on(f: bdd_t, var: u16) -> bool {
+    guard(false, || {
+        let f = unsafe { &*f._p };
+        let var = BddVariable::from_index(var as usize);
+        (2..f.size()).any(|i| f.var_of(BddPointer::from_index(i)) == var)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_nodecount(f: bdd_t) -> usize {
+    guard(0, || unsafe { &*f._p }.size())
+}
+
+/// Node statistics of a BDD (see `bdd_node_stats()`)
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct bdd_node_stats_t {
+    /// Number of terminal nodes (1 for constant BDDs, 2 otherwise)
+    terminal_nodes: usize,
+    /// Number of decision nodes
+    internal_nodes: usize,
+    /// Number of edges from decision nodes to terminals
+    terminal_refs: usize,
+    /// Number of decision nodes with exactly one terminal child
+    nodes_with_one_terminal_child: usize,
+    /// Number of decision nodes whose children are both terminals
+    nodes_with_two_terminal_children: usize,
+}
+
+/// Write a breakdown of the nodes of `f` to `*out`
+#[no_mangle]
+pub unsafe extern "C" fn bdd_node_stats(f: bdd_t, out: *mut bdd_node_stats_t) {
+    guard((), || {
+        let f = unsafe { &*f._p };
+        let mut stats = bdd_node_stats_t {
+            terminal_nodes: f.size().min(2),
+            ..Default::default()
+        };
+        for p in (2..f.size()).map(BddPointer::from_index) {
+            let terminal_children =
+                f.low_link_of(p).is_terminal() as usize + f.high_link_of(p).is_terminal() as usize;
+            stats.internal_nodes += 1;
+            stats.terminal_refs += terminal_children;
+            match terminal_children {
+                1 => stats.nodes_with_one_terminal_child += 1,
+                2 => stats.nodes_with_two_terminal_children += 1,
+                _ => {}
+            }
+        }
+        unsafe { *out = stats };
+    })
+}
+
+/// Count the distinct sub-functions of `f` per variable
+///
+/// `len` must be the number of variables of `f`. Afterwards, `out[i]` is the
+/// number of distinct sub-functions of `f` whose top variable is `i`. Since the
+/// BDD is reduced, these correspond to its decision nodes labeled `i`. Returns
+/// the total number of distinct sub-functions including the constants (i.e.,
+/// the node count), or 0 if `len` does not match.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_cofactor_histogram(f: bdd_t, out: *mut usize, len: usize) -> usize {
+    guard(0, || {
+        let f = unsafe { &*f._p };
+        if len != f.num_vars() as usize {
+            set_error(format!(
+                "Buffer length {len} does not match the variable count {}",
+                f.num_vars()
+            ));
+            return 0;
+        }
+        let out = unsafe { &mut *std::ptr::slice_from_raw_parts_mut(out, len) };
+        out.fill(0);
+        for p in (2..f.size()).map(BddPointer::from_index) {
+            out[f.var_of(p).to_index()] += 1;
+        }
+        f.size()
+    })
+}
+
+/// Number of paths from the root of `f` to the true terminal
+///
+/// In contrast to `bdd_satcount()`, skipped variables do not multiply the
+/// count. The false BDD has 0 paths, the true BDD has 1. The result saturates
+/// at `SIZE_MAX`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_path_count(f: bdd_t) -> usize {
+    guard(0, || {
+        let f = unsafe { &*f._p };
+        // Parents are stored after their children
+        let mut paths: Vec<usize> = Vec::with_capacity(f.size());
+        for p in (0..f.size()).map(BddPointer::from_index) {
+            let n = if p.is_terminal() {
+                p.is_one() as usize
+            } else {
+                paths[f.low_link_of(p).to_index()]
+                    .saturating_add(paths[f.high_link_of(p).to_index()])
+            };
+            paths.push(n);
+        }
+        paths[f.root_pointer().to_index()]
+    })
+}
+
+/// Size of the sum-of-products form of `f` given by its paths
+///
+/// Writes the number of cubes, i.e., the entries of `sat_clauses()` (see
+/// `bdd_path_count()`), to `*out_cubes` and the total number of literals in
+/// these cubes to `*out_literals`. Both are computed in a single pass over the
+/// nodes and saturate at `SIZE_MAX`. The false BDD has 0 cubes and literals,
+/// the true BDD has 1 cube without literals.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_sop_stats(f: bdd_t, out_cubes: *mut usize, out_literals: *mut usize) {
+    guard((), || {
+        let f = unsafe { &*f._p };
+        // Parents are stored after their children. Each path through a node
+        // contributes one literal for the node's variable.
+        let mut stats: Vec<(usize, usize)> = Vec::with_capacity(f.size());
+        for p in (0..f.size()).map(BddPointer::from_index) {
+            let s = if p.is_terminal() {
+                (p.is_one() as usize, 0)
+            } else {
+                let (low_cubes, low_literals) = stats[f.low_link_of(p).to_index()];
+                let (high_cubes, high_literals) = stats[f.high_link_of(p).to_index()];
+                let cubes = low_cubes.saturating_add(high_cubes);
+                let literals = low_literals
+                    .saturating_add(high_literals)
+                    .saturating_add(cubes);
+                (cubes, literals)
+            };
+            stats.push(s);
+        }
+        let (cubes, literals) = stats[f.root_pointer().to_index()];
+        unsafe {
+            *out_cubes = cubes;
+            *out_literals = literals;
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_satcount(f: bdd_t) -> f64 {
+    guard(f64::NAN, || unsafe { &*f._p }.cardinality())
+}
+
+/// Binary logarithm of `bdd_satcount(f)`
+///
+/// Unlike `bdd_satcount()`, this does not overflow for functions over many
+/// variables: the logarithms of the nodes' satisfying fractions are combined
+/// using log-sum-exp. Returns negative infinity for the false BDD.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_satcount_log2(f: bdd_t) -> f64 {
+    guard(f64::NAN, || {
+        let f = unsafe { &*f._p };
+        // Parents are stored after their children
+        let mut logs: Vec<f64> = Vec::with_capacity(f.size());
+        for p in (0..f.size()).map(BddPointer::from_index) {
+            let log = if p.is_zero() {
+                f64::NEG_INFINITY
+            } else if p.is_one() {
+                0.0
+            } else {
+                let a = logs[f.low_link_of(p).to_index()];
+                let b = logs[f.high_link_of(p).to_index()];
+                let max = a.max(b);
+                // `max` is finite as at least one child is satisfiable
+                max + ((a - max).exp2() + (b - max).exp2()).log2() - 1.0
+            };
+            logs.push(log);
+        }
+        logs[f.root_pointer().to_index()] + f.num_vars() as f64
+    })
+}
+
+/// Fraction of all assignments that satisfy `f`, i.e., `bdd_satcount(f)`
+/// divided by 2 to the power of the number of variables
+///
+/// The fraction is computed by averaging the children's fractions at each node,
+/// so it does not overflow for many variables. Only fractions below the
+/// smallest positive `double` are rounded to 0. The false BDD yields 0 and the
+/// true BDD yields 1.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_density(f: bdd_t) -> f64 {
+    guard(f64::NAN, || {
+        let f = unsafe { &*f._p };
+        node_densities(f)[f.root_pointer().to_index()]
+    })
+}
+
+/// Count the satisfying assignments of `f` projected onto `vars`
+///
+/// `vars` must point to `num` variable indices, duplicates are ignored. The
+/// result is the number of assignments to `vars` that can be extended to a
+/// satisfying assignment of `f`. This equals `bdd_satcount()` of `f` with all
+/// other variables quantified existentially, divided by `2^k` where `k` is the
+/// number of other variables. Returns NaN if a variable is out of range.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_satcount_projected(f: bdd_t, vars: *const u16, num: usize) -> f64 {
+    guard(f64::NAN, || {
+        let f = unsafe { &*f._p };
+        let mut keep = vec![false; f.num_vars() as usize];
+        for &var in unsafe { &*std::ptr::slice_from_raw_parts(vars, num) } {
+            match keep.get_mut(var as usize) {
+                Some(keep) => *keep = true,
+                None => {
+                    set_error(format!("Variable {var} out of range"));
+                    return f64::NAN;
+                }
+            }
+        }
+        let others: Vec<BddVariable> = (0..keep.len())
+            .filter(|&i| !keep[i])
+            .map(BddVariable::from_index)
+            .collect();
+        let projection = f.exists(&others);
+        let density = node_densities(&projection)[projection.root_pointer().to_index()];
+        if density == 0.0 {
+            0.0
+        } else {
+            density * 2f64.powi((keep.len() - others.len()) as i32)
+        }
+    })
+}
+
+/// Count the satisfying assignments of `f` under a partial assignment
+///
+/// `values` must point to `num` values (0, 1, or -1), where `num` is the
+/// number of variables of `f`. `f` is restricted to the variables `i` with
+/// `values[i] != -1`, and the satisfying assignments of the result are counted
+/// over the remaining variables. Returns NaN if `num` does not match or
+/// `values` contains an invalid value.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_satcount_given(f: bdd_t, values: *const i8, num: usize) -> f64 {
+    guard(f64::NAN, || {
+        let f = unsafe { &*f._p };
+        let values = match unsafe { read_assignment(values, num) } {
+            Some(values) if num == f.num_vars() as usize => values,
+            Some(_) => {
+                set_error("Assignment length does not match the variable count");
+                return f64::NAN;
+            }
+            None => {
+                set_error("Assignment values must be 0, 1, or -1");
+                return f64::NAN;
+            }
+        };
+        let restricted = f.restrict(&partial_valuation(&values).to_values());
+        let density = node_densities(&restricted)[restricted.root_pointer().to_index()];
+        if density == 0.0 {
+            0.0
+        } else {
+            let free = values.iter().filter(|v| v.is_none()).count();
+            density * 2f64.powi(free as i32)
+        }
+    })
+}
+
+/// Count the assignments that do not satisfy `f`
+///
+/// Equivalent to `bdd_satcount(bdd_not(f))`, but the negation is discarded
+/// right away and does not count towards the manager's node limit. The fraction
+/// of falsifying assignments is computed on the negation directly, so the
+/// result is accurate even if `f` is almost a tautology.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_unsat_count(f: bdd_t) -> f64 {
+    guard(f64::NAN, || {
+        let f = unsafe { &*f._p };
+        let not_f = f.not();
+        let density = node_densities(&not_f)[not_f.root_pointer().to_index()];
+        if density == 0.0 {
+            0.0
+        } else {
+            density * 2f64.powi(f.num_vars() as i32)
+        }
+    })
+}
+
+/// Count the satisfying assignments of `f ∧ g`
+///
+/// Equivalent to `bdd_satcount(bdd_and(f, g))`, but the conjunction is
+/// discarded right away and does not count towards the manager's node limit.
+/// `f` and `g` must belong to the same manager, otherwise NaN is returned.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_and_satcount(f: bdd_t, g: bdd_t) -> f64 {
+    guard(f64::NAN, || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return f64::NAN;
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.and += 1;
+        let g = unsafe { &*g._p };
+        f.and(g).cardinality()
+    })
+}
+
+/// Count the assignments on which `f` and `g` differ
+///
+/// Equivalent to `bdd_satcount(bdd_xor(f, g))`, but like in
+/// `bdd_and_satcount()`, the symmetric difference is discarded right away.
+/// `f` and `g` must belong to the same manager, otherwise NaN is returned.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_xor_satcount(f: bdd_t, g: bdd_t) -> f64 {
+    guard(f64::NAN, || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return f64::NAN;
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.xor += 1;
+        let g = unsafe { &*g._p };
+        f.xor(g).cardinality()
+    })
+}
+
+/// Count the satisfying assignments of `f` with `var` being true and false
+///
+/// Writes `|f ∧ var|` to `*out_true` and `|f ∧ ¬var|` to `*out_false`. These
+/// are also the cardinalities of the cofactors `f|var=1` and `f|var=0` over the
+/// remaining variables, so they sum up to `bdd_satcount(f)`. If `var` is not in
+/// the support of `f`, both are half of `bdd_satcount(f)`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_cofactor_counts(
+    f: bdd_t,
+    var: u16,
+    out_true: *mut f64,
+    out_false: *mut f64,
+) {
+    guard((), || {
+        let f = unsafe { &*f._p };
+        let var = BddVariable::from_index(var as usize);
+        // Fraction of all assignments that satisfy the node's function together
+        // with `var` (index 1) or `¬var` (index 0). As parents are stored after
+        // their children, a single pass suffices.
+        let mut fractions: Vec<[f64; 2]> = Vec::with_capacity(f.size());
+        for p in (0..f.size()).map(BddPointer::from_index) {
+            let res = if p.is_zero() {
+                [0.0, 0.0]
+            } else if p.is_one() {
+                [0.5, 0.5]
+            } else {
+                let low = fractions[f.low_link_of(p).to_index()];
+                let high = fractions[f.high_link_of(p).to_index()];
+                if f.var_of(p) == var {
+                    [(low[0] + low[1]) / 2.0, (high[0] + high[1]) / 2.0]
+                } else {
+                    [(low[0] + high[0]) / 2.0, (low[1] + high[1]) / 2.0]
+                }
+            };
+            fractions.push(res);
+        }
+        let [res_false, res_true] = fractions[f.root_pointer().to_index()];
+        let total = 2.0_f64.powi(f.num_vars() as i32);
+        unsafe {
+            *out_true = res_true * total;
+            *out_false = res_false * total;
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn bdd_eq(f: bdd_t, g: bdd_t) -> bool {
+    guard(false, || {
+        if !unsafe { same_manager(&[f, g]) } {
+            return false;
+        }
+
+        let f = unsafe { &**f._p };
+        let g = unsafe { &**g._p };
+        f == g
+    })
+}
+
+/// Make equal BDDs in `fs` share a handle
+///
+/// `fs` must point to `num` handles of the same manager. Each handle that is
+/// equal (in the sense of `bdd_eq()`) to an earlier one is unreferenced and
+/// replaced by a new reference to the earliest one. Null handles are skipped.
+/// Returns the number of distinct BDDs, or `SIZE_MAX` (leaving `fs` untouched)
+/// if the handles belong to different managers.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_dedup(fs: *mut bdd_t, num: usize) -> usize {
+    guard(0, || {
+        let fs = unsafe { &mut *std::ptr::slice_from_raw_parts_mut(fs, num) };
+        if !unsafe { same_manager(fs) } {
+            return usize::MAX;
+        }
+        let mut unique: HashMap<&Bdd, bdd_t> = HashMap::with_capacity(num);
+        for f in fs.iter_mut().filter(|f| !f._p.is_null()) {
+            let bdd = unsafe { &(*f._p).bdd };
+            match unique.get(bdd) {
+                Some(&first) if first._p != f._p => unsafe {
+                    bdd_unref(*f);
+                    *f = bdd_ref(first);
+                },
+                Some(_) => {}
+                None => {
+                    unique.insert(bdd, *f);
+                }
+            }
+        }
+        unique.len()
+    })
+}
+
+/// Coudert & Madre's constrain operator (generalized cofactor)
+fn constrain_rec(
+    f: &Bdd,
+    p: BddPointer,
+    c: &Bdd,
+    q: BddPointer,
+    builder: &mut BddBuilder,
+    cache: &mut HashMap<(BddPointer, BddPointer), BddPointer>,
+) -> BddPointer {
+    if p.is_terminal() || q.is_zero() {
+        return if q.is_zero() { q } else { p };
+    }
+    if let Some(&res) = cache.get(&(p, q)) {
+        return res;
+    }
+    let x = f.var_of(p).min(c.var_of(q));
+    let cofactors = |bdd: &Bdd, r: BddPointer| {
+        if bdd.var_of(r) == x {
+            (bdd.low_link_of(r), bdd.high_link_of(r))
+        } else {
+            (r, r)
+        }
+    };
+    let (f_low, f_high) = cofactors(f, p);
+    let (c_low, c_high) = cofactors(c, q);
+    let res = if c_low.is_zero() {
+        constrain_rec(f, f_high, c, c_high, builder, cache)
+    } else if c_high.is_zero() {
+        constrain_rec(f, f_low, c, c_low, builder, cache)
+    } else {
+        let low = constrain_rec(f, f_low, c, c_low, builder, cache);
+        let high = constrain_rec(f, f_high, c, c_high, builder, cache);
+        builder.mk_node(x, low, high)
+    };
+    cache.insert((p, q), res);
+    res
+}
+
+/// Generalized cofactor of `f` with respect to `c`
+///
+/// Implements Coudert & Madre's constrain operator, so the result agrees with
+/// `f` on all assignments satisfying `c`, i.e., `constrain(f, c) ∧ c == f ∧ c`.
+/// Each assignment outside `c` is mapped to a "nearest" assignment in `c`. If
+/// `c` is true, the result is `f`. If `c` is false, we return false. Unlike
+/// `bdd_simplify()`, the result may depend on variables that `f` does not
+/// depend on. `f` and `c` must belong to the same manager.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_constrain(f: bdd_t, c: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        if !unsafe { same_manager(&[f, c]) } {
+            return bdd_t::null();
+        }
+
+        let f = unsafe { &*f._p };
+        unsafe { &mut *f.manager }.stats.cofactor += 1;
+        let c = unsafe { &**c._p };
+        let mut builder = BddBuilder::new(f.num_vars());
+        let root = constrain_rec(
+            f,
+            f.root_pointer(),
+            c,
+            c.root_pointer(),
+            &mut builder,
+            &mut HashMap::new(),
+        );
+        unsafe { bdd_t::from_bdd(builder.build(root), f.manager) }
+    })
+}
+
+/// Whether `f` and `g` agree on all assignments satisfying `care`
+///
+/// All three BDDs must belong to the same manager, otherwise the result is
+/// false.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_eq_on(f: bdd_t, g: bdd_t, care: bdd_t) -> bool {
+    guard(false, || {
+        if !unsafe { same_manager(&[f, g, care]) } {
+            return false;
+        }
+
+        let f = unsafe { &*f._p };
+        let g = unsafe { &*g._p };
+        let care = unsafe { &*care._p };
+        care.and(&f.xor(g)).is_false()
+    })
+}
+
+/// Whether `f` and `g` belong to the same manager
+///
+/// Binary operations require this.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_same_manager(f: bdd_t, g: bdd_t) -> bool {
+    guard(false, || unsafe { (*f._p).manager == (*g._p).manager })
+}
+
+/// Pick a satisfying cube by walking down from the root of `f`
+///
+/// We always follow the preferred edge unless it leads to the false terminal.
+/// `f` must not be false. `set(i, v)` is called for each variable `i` on the
+/// path, all other variables are don't-cares.
+fn pick_cube(f: &Bdd, high_first: bool, mut set: impl FnMut(usize, OptBool)) {
+    debug_assert!(!f.is_false());
+    let mut p = f.root_pointer();
+    while !p.is_one() {
+        let (low, high) = (f.low_link_of(p), f.high_link_of(p));
+        let take_high = if high_first {
+            !high.is_zero()
+        } else {
+            low.is_zero()
+        };
+        let (c, value) = if take_high {
+            (high, OptBool::True)
+        } else {
+            (low, OptBool::False)
+        };
+        debug_assert!(!c.is_zero());
+        set(f.var_of(p).to_index(), value);
+        p = c;
+    }
+}
+
+fn pick_cube_vec(f: &Bdd, high_first: bool) -> bdd_assignment_t {
+    if f.is_false() {
+        return bdd_assignment_t::null();
+    }
+    let mut assignment = vec![OptBool::None; f.num_vars() as usize];
+    pick_cube(f, high_first, |i, v| assignment[i] = v);
+    bdd_assignment_t::from_vec(assignment)
+}
+
+/// Pick a satisfying cube of `f`
+///
+/// Starting at the root, this follows the low edge of each node unless it
+/// leads to the false terminal. Variables not on the path are don't-cares. As
+/// BDDs are canonical, the result is deterministic: it only depends on the
+/// function `f` represents and its number of variables. For the false BDD, the
+/// returned assignment has a null `data` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_pickcube(f: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        pick_cube_vec(unsafe { &*f._p }, false)
+    })
+}
+
+/// Like `bdd_pickcube()`, but prefer the high edges
+#[no_mangle]
+pub unsafe extern "C" fn bdd_pickcube_high_first(f: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        pick_cube_vec(unsafe { &*f._p }, true)
+    })
+}
+
+/// Like `bdd_pickcube()`, but write the cube to the caller-provided `out`
+///
+/// `len` must be the number of variables of `f`. Returns 1 on success, 0 if
+/// `f` is false (leaving `out` untouched), and -1 if `len` does not match.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_pickcube_into(f: bdd_t, out: *mut i8, len: usize) -> i8 {
+    guard(-1, || {
+        let f = unsafe { &*f._p };
+        if len != f.num_vars() as usize {
+            set_error(format!(
+                "Buffer length {len} does not match the variable count {}",
+                f.num_vars()
+            ));
+            return -1;
+        }
+        if f.is_false() {
+            return 0;
+        }
+        let out = unsafe { &mut *std::ptr::slice_from_raw_parts_mut(out, len) };
+        out.fill(OptBool::None as i8);
+        pick_cube(f, false, |i, v| out[i] = v as i8);
+        1
+    })
+}
+
+/// Pick a satisfying cube of `f` with as few literals as possible
+///
+/// The result is the cube of a path to the true terminal with the fewest
+/// decision nodes, i.e., the entry of `sat_clauses()` with the most don't
+/// cares. Note that a cube not corresponding to a path might be even shorter.
+/// For the false BDD, the assignment has a null `data` pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_shortest_cube(f: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        let f = unsafe { &*f._p };
+        let Some(clause) = f.most_free_clause() else {
+            return bdd_assignment_t::null();
+        };
+        let mut assignment = vec![OptBool::None; f.num_vars() as usize];
+        for (var, value) in clause.to_values() {
+            assignment[var.to_index()] = if value { OptBool::True } else { OptBool::False };
+        }
+        bdd_assignment_t::from_vec(assignment)
+    })
+}
+
+/// Pick a random satisfying assignment of `f`
+///
+/// Each satisfying assignment is chosen with the same probability: at every
+/// node, we follow an edge with probability proportional to the number of
+/// satisfying assignments below it, and variables that are skipped are chosen
+/// uniformly. The result only depends on `f` and `seed`. All entries of the
+/// result are 0 or 1. For the false BDD, the assignment has a null `data`
+/// pointer.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_random_sat(f: bdd_t, seed: u64) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        let f = unsafe { &*f._p };
+        if f.is_false() {
+            return bdd_assignment_t::null();
+        }
+        let densities = node_densities(f);
+        let mut rng = SplitMix64(seed);
+        let mut assignment = vec![OptBool::None; f.num_vars() as usize];
+        let mut p = f.root_pointer();
+        while !p.is_one() {
+            let (low, high) = (f.low_link_of(p), f.high_link_of(p));
+            let (d_low, d_high) = (densities[low.to_index()], densities[high.to_index()]);
+            let (c, value) = if rng.next_f64() * (d_low + d_high) < d_high {
+                (high, OptBool::True)
+            } else {
+                (low, OptBool::False)
+            };
+            assignment[f.var_of(p).to_index()] = value;
+            p = c;
+        }
+        for value in assignment.iter_mut().filter(|v| **v == OptBool::None) {
+            *value = if rng.next_u64() & 1 != 0 {
+                OptBool::True
+            } else {
+                OptBool::False
+            };
+        }
+        bdd_assignment_t::from_vec(assignment)
+    })
+}
+
+/// Write the `index`-th satisfying assignment of `f` to `out`
+///
+/// Assignments are ordered lexicographically, with variable 0 being the most
+/// significant and false preceding true, so index 0 is the smallest satisfying
+/// assignment. Fractional indices are rounded down. `len` must be the number of
+/// variables of `f`, and all written values are 0 or 1. Returns false (leaving
+/// `out` untouched) if `len` does not match or `index` is not in the range
+/// `[0, bdd_satcount(f))`. Indices are only exact up to 2^53.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_nth_sat(f: bdd_t, index: f64, out: *mut i8, len: usize) -> bool {
+    guard(false, || {
+        let f = unsafe { &*f._p };
+        if len != f.num_vars() as usize {
+            set_error(format!(
+                "Buffer length {len} does not match the variable count {}",
+                f.num_vars()
+            ));
+            return false;
+        }
+        if !(index >= 0.0 && index < f.cardinality()) {
+            return false;
+        }
+        let out = unsafe { &mut *std::ptr::slice_from_raw_parts_mut(out, len) };
+        let densities = node_densities(f);
+        let mut index = index.floor();
+        let mut p = f.root_pointer();
+        for (i, value) in out.iter_mut().enumerate() {
+            // Number of assignments to the variables after `i`
+            let half = 2f64.powi((len - i - 1) as i32);
+            let (low, high) = if f.var_of(p).to_index() == i {
+                (f.low_link_of(p), f.high_link_of(p))
+            } else {
+                (p, p)
+            };
+            let d_low = densities[low.to_index()];
+            let low_count = if d_low == 0.0 { 0.0 } else { d_low * half };
+            if index < low_count {
+                *value = OptBool::False as i8;
+                p = low;
+            } else {
+                *value = OptBool::True as i8;
+                index -= low_count;
+                p = high;
+            }
+        }
+        true
+    })
+}
+
+/// Literals implied by `f`
+///
+/// Entry `i` of the result is 0 or 1 if variable `i` has this value in all
+/// satisfying assignments of `f`, and -1 otherwise. Both the false and the
+/// true BDD yield an assignment with all entries being -1.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_essential_literals(f: bdd_t) -> bdd_assignment_t {
+    guard(bdd_assignment_t::null(), || {
+        let f = unsafe { &*f._p };
+        let mut assignment = vec![OptBool::None; f.num_vars() as usize];
+        if let Some(clause) = f.necessary_clause() {
+            for (var, value) in clause.to_values() {
+                assignment[var.to_index()] = if value { OptBool::True } else { OptBool::False };
+            }
+        }
+        bdd_assignment_t::from_vec(assignment)
+    })
+}
+
+/// Conjunction of the literals implied by `f` (see `bdd_essential_literals()`)
+///
+/// This is the smallest cube containing all satisfying assignments of `f`. For
+/// the false BDD, the result is false, and if `f` implies no literal, it is
+/// true.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_implied_cube(f: bdd_t) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let f = unsafe { &*f._p };
+        let bdd = match f.necessary_clause() {
+            Some(clause) => {
+                let mut literals = clause.to_values();
+                literals.sort();
+                cube(f.num_vars(), &literals)
+            }
+            None => BddBuilder::new(f.num_vars()).build(BddPointer::zero()),
+        };
+        unsafe { bdd_t::from_bdd(bdd, f.manager) }
+    })
+}
+
+/// Pass each clause to `cb` as DIMACS literals (see `bdd_to_dnf()`)
+fn emit_clauses(
+    clauses: impl Iterator<Item = BddPartialValuation>,
+    negate: bool,
+    cb: extern "C" fn(*const i32, usize, *mut std::ffi::c_void),
+    ctx: *mut std::ffi::c_void,
+) {
+    let mut literals: Vec<i32> = Vec::new();
+    for clause in clauses {
+        literals.clear();
+        literals.extend(clause.to_values().into_iter().map(|(var, value)| {
+            let lit = var.to_index() as i32 + 1;
+            if value != negate {
+                lit
+            } else {
+                -lit
+            }
+        }));
+        cb(literals.as_ptr(), literals.len(), ctx);
+    }
+}
+
+/// Enumerate the cubes of a disjunctive normal form of `f`
+///
+/// `cb` is called once per cube with an array of literals and its length, as
+/// well as `ctx`. Literals follow the DIMACS convention: variable `i` is
+/// encoded as `i + 1` if it occurs positively and as `-(i + 1)` if it occurs
+/// negatively. Literals are sorted by variable, and the array is only valid
+/// during the call. The cubes are pairwise disjoint. For the false BDD, `cb` is
+/// never called, for the true BDD, it is called once with an empty cube.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_dnf(
+    f: bdd_t,
+    cb: extern "C" fn(*const i32, usize, *mut std::ffi::c_void),
+    ctx: *mut std::ffi::c_void,
+) {
+    guard((), || {
+        let f = unsafe { &**f._p };
+        emit_clauses(f.sat_clauses(), false, cb, ctx);
+    })
+}
+
+/// Enumerate the clauses of a conjunctive normal form of `f`
+///
+/// The clauses are the negated cubes of `bdd_to_dnf()` applied to `¬f`, using
+/// the same literal encoding. For the true BDD, `cb` is never called, for the
+/// false BDD, it is called once with an empty clause.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_cnf(
+    f: bdd_t,
+    cb: extern "C" fn(*const i32, usize, *mut std::ffi::c_void),
+    ctx: *mut std::ffi::c_void,
+) {
+    guard((), || {
+        let f = unsafe { &**f._p };
+        emit_clauses(f.not().sat_clauses(), true, cb, ctx);
+    })
+}
+
+/// Parse a CNF in DIMACS format into a list of clauses
+///
+/// Each clause is a list of DIMACS literals. `max_vars` is the maximum number
+/// of variables the header may declare.
+fn parse_dimacs(input: &str, max_vars: u16) -> Result<Vec<Vec<i32>>, String> {
+    let mut num_vars: Option<u32> = None;
+    let mut num_clauses = 0;
+    let mut clauses: Vec<Vec<i32>> = Vec::new();
+    let mut clause: Vec<i32> = Vec::new();
+    for (line_no, line) in input.lines().enumerate().map(|(i, l)| (i + 1, l.trim())) {
+        if line.is_empty() || line.starts_with('c') {
+            continue;
+        }
+        if line.starts_with('%') {
+            // End marker used by some benchmark sets
+            break;
+        }
+        if line.starts_with('p') {
+            let header: Vec<&str> = line.split_whitespace().collect();
+            let (vars, num) = match header[..] {
+                ["p", "cnf", vars, num] if num_vars.is_none() => (vars.parse(), num.parse()),
+                _ => return Err(format!("Line {line_no}: Invalid problem line")),
+            };
+            let (Ok(vars), Ok(num)) = (vars, num) else {
+                return Err(format!("Line {line_no}: Invalid problem line"));
+            };
+            if vars > max_vars as u32 {
+                return Err(format!(
+                    "The CNF has {vars} variables, but the manager has only {max_vars}"
+                ));
+            }
+            num_vars = Some(vars);
+            num_clauses = num;
+            continue;
+        }
+        let Some(num_vars) = num_vars else {
+            return Err(format!("Line {line_no}: Clause before the problem line"));
+        };
+        for token in line.split_whitespace() {
+            let Ok(lit) = token.parse::<i32>() else {
+                return Err(format!("Line {line_no}: Invalid literal '{token}'"));
+            };
+            if lit == 0 {
+                clauses.push(std::mem::take(&mut clause));
+            } else if lit.unsigned_abs() > num_vars {
+                return Err(format!("Line {line_no}: Literal {lit} is out of range"));
+            } else {
+                clause.push(lit);
+            }
+        }
+    }
+    if num_vars.is_none() {
+        return Err("Missing problem line".to_string());
+    }
+    if !clause.is_empty() {
+        return Err("Last clause is not terminated by 0".to_string());
+    }
+    if clauses.len() != num_clauses {
+        return Err(format!(
+            "Expected {num_clauses} clauses, found {}",
+            clauses.len()
+        ));
+    }
+    Ok(clauses)
+}
+
+/// Read a CNF in DIMACS format from the file at `path` and construct its BDD
+///
+/// Variable `i + 1` of the CNF is mapped to variable `i` of `manager` (see
+/// `bdd_to_cnf()`), so the number of variables declared by the CNF must not
+/// exceed the manager's. Each clause is built as a disjunction of its literals,
+/// and the clauses are conjoined as a balanced tree. Returns a null handle if
+/// the file cannot be read or is malformed. See `bdd_save()` for the
+/// requirements on `path`.
+#[no_mangle]
+pub unsafe extern "C" fn manager_from_dimacs(
+    manager: manager_t,
+    path: *const std::ffi::c_char,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let m = unsafe { &*manager._p };
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => return bdd_t::error(err),
+        };
+        let input = match std::fs::read_to_string(path) {
+            Ok(input) => input,
+            Err(err) => return bdd_t::error(format!("Cannot read '{}': {err}", path.display())),
+        };
+        let clauses = match parse_dimacs(&input, m.num_vars()) {
+            Ok(clauses) => clauses,
+            Err(err) => return bdd_t::error(format!("Cannot parse '{}': {err}", path.display())),
+        };
+        let clauses: Vec<Bdd> = clauses
+            .iter()
+            .map(|clause| {
+                let literals = clause.iter().map(|&lit| {
+                    let var = BddVariable::from_index(lit.unsigned_abs() as usize - 1);
+                    literal(m.num_vars(), var, lit > 0)
+                });
+                let empty = BddBuilder::new(m.num_vars()).build(BddPointer::zero());
+                literals.fold(empty, |acc, lit| acc.or(&lit))
+            })
+            .collect();
+        let bdd = if clauses.is_empty() {
+            BddBuilder::new(m.num_vars()).build(BddPointer::one())
+        } else {
+            fold_balanced(clauses, Bdd::and)
+        };
+        unsafe { bdd_t::from_bdd(bdd, manager._p) }
+    })
+}
+
+/// Enumerate the prime implicants of `f` that are not implicants of any of the
+/// `filters` (see `bdd_prime_implicants()`)
+///
+/// Returns false if `emit` requested to stop.
+fn prime_implicants_rec(
+    f: &Bdd,
+    filters: &[Bdd],
+    cube: &mut [i8],
+    emit: &mut impl FnMut(&[i8]) -> bool,
+) -> bool {
+    // If `f` implies a filter, so does each of its implicants
+    if f.is_false() || filters.iter().any(|g| f.and_not(g).is_false()) {
+        return true;
+    }
+    if f.is_true() {
+        return emit(cube);
+    }
+    let x = f.var_of(f.root_pointer());
+    let f0 = f.var_restrict(x, false);
+    let f1 = f.var_restrict(x, true);
+
+    // A prime implicant without `x` is a prime implicant of `f0 ∧ f1`. A cube
+    // without `x` implies `g` iff it implies `g0 ∧ g1`.
+    let filters_both: Vec<Bdd> = filters
+        .iter()
+        .map(|g| g.var_restrict(x, false).and(&g.var_restrict(x, true)))
+        .collect();
+    if !prime_implicants_rec(&f0.and(&f1), &filters_both, cube, emit) {
+        return false;
+    }
+
+    // A prime implicant with the literal `x = v` is `x = v` conjoined with a
+    // prime implicant of `fv` that does not imply the other cofactor.
+    for (value, fv, other) in [(false, &f0, &f1), (true, &f1, &f0)] {
+        let mut filters_v: Vec<Bdd> = filters.iter().map(|g| g.var_restrict(x, value)).collect();
+        filters_v.push(other.clone());
+        cube[x.to_index()] = value as i8;
+        let proceed = prime_implicants_rec(fv, &filters_v, cube, emit);
+        cube[x.to_index()] = OptBool::None as i8;
+        if !proceed {
+            return false;
+        }
+    }
+    true
+}
+
+/// Enumerate the prime implicants of `f`
+///
+/// `cb` is called once per prime implicant with an array of values (0, 1, or
+/// -1 for don't care) for each variable, its length, and `ctx`. The array is
+/// only valid during the call. If `cb` returns false, the enumeration stops.
+/// For the false BDD, `cb` is never called, for the true BDD, it is called once
+/// with a cube consisting of don't cares only.
+///
+/// The prime implicants are enumerated recursively along the variable order:
+/// for the top variable `x`, those of `f|x=0 ∧ f|x=1` come first, followed by
+/// those of `f|x=0` and `f|x=1` that do not imply the other cofactor, extended
+/// by `¬x` and `x`, respectively.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_prime_implicants(
+    f: bdd_t,
+    cb: extern "C" fn(*const i8, usize, *mut std::ffi::c_void) -> bool,
+    ctx: *mut std::ffi::c_void,
+) {
+    guard((), || {
+        let f = unsafe { &**f._p };
+        let mut cube = vec![OptBool::None as i8; f.num_vars() as usize];
+        prime_implicants_rec(f, &[], &mut cube, &mut |cube| {
+            cb(cube.as_ptr(), cube.len(), ctx)
+        });
+    })
+}
+
+/// Greedily cover the satisfying assignments of `f` with large cubes
+///
+/// Repeatedly takes the cube of a path with the most don't cares in the part of
+/// `f` not covered yet, enlarges it by dropping literals as long as it stays
+/// within this part, and passes it to `cb`. Hence, the cubes are pairwise
+/// disjoint, and together they cover `f`. `cb` is called with an array of values
+/// (0, 1, or -1 for don't care) for each variable, its length, and `ctx`. The
+/// array is only valid during the call. If `cb` returns false, the enumeration
+/// stops.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_cube_cover(
+    f: bdd_t,
+    cb: extern "C" fn(*const i8, usize, *mut std::ffi::c_void) -> bool,
+    ctx: *mut std::ffi::c_void,
+) {
+    guard((), || {
+        let f = unsafe { &**f._p };
+        let mut values = vec![OptBool::None as i8; f.num_vars() as usize];
+        let mut remaining = f.clone();
+        while let Some(clause) = remaining.most_free_clause() {
+            let mut literals = clause.to_values();
+            let mut i = 0;
+            while i < literals.len() {
+                let literal = literals.remove(i);
+                if !cube(f.num_vars(), &literals).and_not(&remaining).is_false() {
+                    literals.insert(i, literal);
+                    i += 1;
+                }
+            }
+
+            values.fill(OptBool::None as i8);
+            for &(var, value) in &literals {
+                values[var.to_index()] = value as i8;
+            }
+            if !cb(values.as_ptr(), values.len(), ctx) {
+                return;
+            }
+            remaining = remaining.and_not(&cube(f.num_vars(), &literals));
+        }
+    })
+}
+
+/// Free a string returned by this library
+#[no_mangle]
+pub unsafe extern "C" fn bdd_string_free(s: *mut std::ffi::c_char) {
+    guard((), || {
+        if !s.is_null() {
+            drop(unsafe { CString::from_raw(s) });
+        }
+    })
+}
+
+/// Describe the node table of `f` as JSON
+///
+/// The result has the form
+/// `{"num_vars":N,"root":R,"terminals":[...],"nodes":[...]}`. `terminals`
+/// contains `{"id":0,"value":false}` and, unless `f` is false,
+/// `{"id":1,"value":true}`. Each entry of `nodes` has the form
+/// `{"id":I,"var":V,"low":L,"high":H}` where `V` is a variable index and `L`
+/// and `H` are IDs of nodes or terminals. Children precede their parents. The
+/// string must be freed using `bdd_string_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_json(f: bdd_t) -> *mut std::ffi::c_char {
+    guard(std::ptr::null_mut(), || {
+        use std::fmt::Write;
+
+        let f = unsafe { &*f._p };
+        let mut json = format!(
+            "{{\"num_vars\":{},\"root\":{},\"terminals\":[{{\"id\":0,\"value\":false}}",
+            f.num_vars(),
+            f.root_pointer().to_index()
+        );
+        if !f.is_false() {
+            json.push_str(",{\"id\":1,\"value\":true}");
+        }
+        json.push_str("],\"nodes\":[");
+        for p in (2..f.size()).map(BddPointer::from_index) {
+            if p.to_index() > 2 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"id\":{},\"var\":{},\"low\":{},\"high\":{}}}",
+                p.to_index(),
+                f.var_of(p).to_index(),
+                f.low_link_of(p).to_index(),
+                f.high_link_of(p).to_index()
+            )
+            .unwrap();
+        }
+        json.push_str("]}");
+        CString::new(json).unwrap().into_raw()
+    })
+}
+
+/// String writer that fails once more than `remaining` characters are written
+struct TruncatingWriter {
+    out: String,
+    remaining: usize,
+}
+
+impl std::fmt::Write for TruncatingWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for c in s.chars() {
+            if self.remaining == 0 {
+                return Err(std::fmt::Error);
+            }
+            self.out.push(c);
+            self.remaining -= 1;
+        }
+        Ok(())
+    }
+}
+
+/// Write the sub-function of `f` at the decision node `p` as an expression
+///
+/// This produces the same output as formatting the result of lib-bdd's
+/// `Bdd::to_boolean_expression()`, but without materializing the (potentially
+/// exponentially large) expression tree.
+fn write_expression(
+    f: &Bdd,
+    p: BddPointer,
+    vars: &BddVariableSet,
+    out: &mut impl std::fmt::Write,
+) -> std::fmt::Result {
+    let name = vars.name_of(f.var_of(p));
+    let (low, high) = (f.low_link_of(p), f.high_link_of(p));
+    match (low.is_terminal(), high.is_terminal()) {
+        (true, true) if high.is_one() => write!(out, "{name}"),
+        (true, true) => write!(out, "!{name}"),
+        (true, false) => {
+            if low.is_zero() {
+                write!(out, "({name} & ")?;
+            } else {
+                write!(out, "(!{name} | ")?;
+            }
+            write_expression(f, high, vars, out)?;
+            write!(out, ")")
+        }
+        (false, true) => {
+            if high.is_zero() {
+                write!(out, "(!{name} & ")?;
+            } else {
+                write!(out, "({name} | ")?;
+            }
+            write_expression(f, low, vars, out)?;
+            write!(out, ")")
+        }
+        (false, false) => {
+            write!(out, "(({name} & ")?;
+            write_expression(f, high, vars, out)?;
+            write!(out, ") | (!{name} & ")?;
+            write_expression(f, low, vars, out)?;
+            write!(out, "))")
+        }
+    }
+}
+
+/// Render `f` as a Boolean expression of at most `max_len` characters
+///
+/// The expression uses the manager's variable names and the operators `!`,
+/// `&`, and `|`. Since it may be exponentially larger than the BDD, rendering
+/// stops once `max_len` characters are written, and "…" is appended. Constant
+/// BDDs are rendered as `true` or `false` regardless of `max_len`. The string
+/// must be freed using `bdd_string_free()`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_expression_truncated(
+    f: bdd_t,
+    max_len: usize,
+) -> *mut std::ffi::c_char {
+    guard(std::ptr::null_mut(), || {
+        let f = unsafe { &*f._p };
+        let res = if f.is_false() {
+            "false".to_string()
+        } else if f.is_true() {
+            "true".to_string()
+        } else {
+            let mut writer = TruncatingWriter {
+                out: String::new(),
+                remaining: max_len,
+            };
+            let vars = &unsafe { &*f.manager }.var_set;
+            if write_expression(f, f.root_pointer(), vars, &mut writer).is_err() {
+                writer.out.push('…');
+            }
+            writer.out
+        };
+        CString::new(res.replace('\0', "")).unwrap().into_raw()
+    })
+}
+
+/// Save `f` to the file at `path`
+///
+/// On Unix, `path` may be an arbitrary byte string, on other platforms it must
+/// be valid UTF-8. Returns 0 on success and -1 on error.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_save(f: bdd_t, path: *const std::ffi::c_char) -> i32 {
+    guard(-1, || {
+        let f = unsafe { &**f._p };
+        let f_bytes = f.to_bytes();
+
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => {
+                set_error(err);
+                return -1;
+            }
+        };
+        match std::fs::write(path, f_bytes) {
+            Ok(()) => 0,
+            Err(err) => {
+                set_error(format!("Cannot write '{}': {err}", path.display()));
+                -1
+            }
+        }
+    })
+}
+
+/// Write satisfying assignments of `f` to a CSV file at `path`
+///
+/// The first row contains the names of the variables in the support of `f`,
+/// each further row one satisfying assignment to these variables with a column
+/// of 0s and 1s per variable. Variables outside the support are omitted since
+/// `f` does not depend on them. At most `max_rows` assignments are written, or
+/// all of them if `max_rows` is 0, which may take very long and produce a huge
+/// file. Returns the number of assignments written or -1 on error. See
+/// `bdd_save()` for the requirements on `path`.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_sat_to_csv(
+    f: bdd_t,
+    path: *const std::ffi::c_char,
+    max_rows: usize,
+) -> i64 {
+    guard(-1, || {
+        use std::io::Write;
+
+        fn csv_field(s: &str) -> std::borrow::Cow<'_, str> {
+            if s.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", s.replace('"', "\"\"")).into()
+            } else {
+                s.into()
+            }
+        }
+
+        let f = unsafe { &*f._p };
+        let m = unsafe { &*f.manager };
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => {
+                set_error(err);
+                return -1;
+            }
+        };
+        let max_rows = if max_rows == 0 { usize::MAX } else { max_rows };
+        let support_set = f.support_set();
+        let mut support: Vec<BddVariable> = support_set.iter().copied().collect();
+        support.sort();
+        // Fix the other variables to false such that each assignment to the support
+        // is enumerated once
+        let others: Vec<(BddVariable, bool)> = (0..f.num_vars() as usize)
+            .map(BddVariable::from_index)
+            .filter(|var| !support_set.contains(var))
+            .map(|var| (var, false))
+            .collect();
+        let projected = f.and(&cube(f.num_vars(), &others));
+        let mut rows = 0;
+        let res = std::fs::File::create(path).and_then(|file| {
+            let mut file = std::io::BufWriter::new(file);
+            let names: Vec<String> = support
+                .iter()
+                .map(|&var| {
+                    if var.to_index() < m.num_vars() as usize {
+                        csv_field(&m.name_of(var)).into_owned()
+                    } else {
+                        format!("x_{}", var.to_index())
+                    }
+                })
+                .collect();
+            writeln!(file, "{}", names.join(","))?;
+            let mut line = String::new();
+            for valuation in projected.sat_valuations().take(max_rows) {
+                line.clear();
+                for &var in &support {
+                    line.push(if valuation.value(var) { '1' } else { '0' });
+                    line.push(',');
+                }
+                line.pop();
+                writeln!(file, "{line}")?;
+                rows += 1;
+            }
+            file.flush()
+        });
+        match res {
+            Ok(()) => rows as i64,
+            Err(err) => {
+                set_error(format!("Cannot write '{}': {err}", path.display()));
+                -1
+            }
+        }
+    })
+}
+
+/// `std::io::Write` adapter for a C write callback
+struct CallbackWriter {
+    write: extern "C" fn(*const u8, usize, *mut std::ffi::c_void) -> isize,
+    ctx: *mut std::ffi::c_void,
+}
+
+impl std::io::Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match (self.write)(buf.as_ptr(), buf.len(), self.ctx) {
+            n if n < 0 => Err(std::io::Error::other(format!(
+                "Write callback failed with {n}"
+            ))),
+            n => Ok(n as usize),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `std::io::Read` adapter for a C read callback
+struct CallbackReader {
+    read: extern "C" fn(*mut u8, usize, *mut std::ffi::c_void) -> isize,
+    ctx: *mut std::ffi::c_void,
+}
+
+impl std::io::Read for CallbackReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match (self.read)(buf.as_mut_ptr(), buf.len(), self.ctx) {
+            n if n < 0 => Err(std::io::Error::other(format!(
+                "Read callback failed with {n}"
+            ))),
+            n => Ok(n as usize),
+        }
+    }
+}
+
+/// Serialize `f` in the format of `bdd_save()`, passing the bytes to `write`
+///
+/// `write(data, len, ctx)` is called repeatedly with chunks of the
+/// serialization. It must return the number of bytes it consumed (at most
+/// `len`, the remaining bytes are passed again) or a negative value to abort.
+/// Returns 0 on success and -1 if the callback aborted or did not make progress.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_to_bytes_cb(
+    f: bdd_t,
+    write: extern "C" fn(*const u8, usize, *mut std::ffi::c_void) -> isize,
+    ctx: *mut std::ffi::c_void,
+) -> i32 {
+    guard(-1, || {
+        use std::io::Write;
+
+        let f = unsafe { &**f._p };
+        let mut writer =
+            std::io::BufWriter::with_capacity(1024 * 10, CallbackWriter { write, ctx });
+        match f.write_as_bytes(&mut writer).and_then(|_| writer.flush()) {
+            Ok(()) => 0,
+            Err(err) => {
+                set_error(format!("Cannot write BDD: {err}"));
+                -1
+            }
+        }
+    })
+}
+
+/// Deserialize a BDD produced by `bdd_save()` or `bdd_to_bytes_cb()`
+///
+/// `read(buf, len, ctx)` must write at most `len` bytes to `buf` and return
+/// their number, 0 at the end of the input, or a negative value on error. As
+/// with `bdd_load()`, the BDD does not track the manager's number of variables.
+/// Returns a null handle on error.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_from_bytes_cb(
+    manager: manager_t,
+    read: extern "C" fn(*mut u8, usize, *mut std::ffi::c_void) -> isize,
+    ctx: *mut std::ffi::c_void,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        // See `bdd_load()` for the buffer size
+        let mut reader = std::io::BufReader::with_capacity(1024 * 10, CallbackReader { read, ctx });
+        let f = match Bdd::read_as_bytes(&mut reader) {
+            Ok(f) => f,
+            Err(err) => return bdd_t::error(format!("Cannot read BDD: {err}")),
+        };
+        let mut f = match Bdd::from_nodes(&f.to_nodes()) {
+            Ok(f) => f,
+            Err(err) => return bdd_t::error(format!("Invalid BDD: {err}")),
+        };
+        unsafe { f.set_num_vars(u16::MAX) };
+        unsafe { bdd_t::from_bdd(f, manager._p) }
+    })
+}
+
+/// Load a BDD saved using `bdd_save()`
+///
+/// See `bdd_save()` for the requirements on `path`. Returns a null handle if
+/// the file cannot be read.
+#[no_mangle]
+pub unsafe extern "C" fn bdd_load(manager: manager_t, path: *const std::ffi::c_char) -> bdd_t {
+    guard(bdd_t::null(), || {
+        // Taken from Samuel Pastva and Thomas Henzinger's source code in 'util.rs'
+        // in their artifact: https://zenodo.org/records/7958052
+
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => return bdd_t::error(err),
+        };
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(err) => return bdd_t::error(format!("Cannot open '{}': {err}", path.display())),
+        };
+
+        // Each chunk is 10 bytes large, so we use a multiple of 10. In older
+        // versions of LibBDD, not using a multiple of 10 even caused errors:
+        // https://github.com/sybila/biodivine-lib-bdd/issues/34
+        let mut file = std::io::BufReader::with_capacity(1024 * 10, file);
+        let mut f = match Bdd::read_as_bytes(&mut file) {
+            Ok(f) => f,
+            Err(err) => {
+                return bdd_t::error(format!("Cannot read BDD from '{}': {err}", path.display()))
+            }
+        };
+
+        // Ensure this `Bdd` is compatible with any other loaded using this very
+        // function (i.e., it tracks the same number of variables).
+        unsafe { f.set_num_vars(u16::MAX) };
+
+        unsafe { bdd_t::from_bdd(f, manager._p) }
+    })
+}
+
+/// Magic bytes at the beginning of files written by `manager_save()`
+const ARCHIVE_MAGIC: &[u8; 8] = b"LBDDARC2";
+
+fn write_archive(
+    out: &mut impl std::io::Write,
+    m: &Manager,
+    named: &[(&std::ffi::CStr, &Bdd)],
+) -> std::io::Result<()> {
+    fn write_str(out: &mut impl std::io::Write, s: &[u8]) -> std::io::Result<()> {
+        out.write_all(&(s.len() as u32).to_le_bytes())?;
+        out.write_all(s)
+    }
+
+    out.write_all(ARCHIVE_MAGIC)?;
+    out.write_all(&(m.max_nodes_total as u64).to_le_bytes())?;
+    out.write_all(&m.num_vars().to_le_bytes())?;
+    // A flag per variable tells whether its name follows
+    for i in 0..m.num_vars() as usize {
+        match m.var_names.get(i).and_then(Option::as_ref) {
+            Some(name) => {
+                out.write_all(&[1])?;
+                write_str(out, name.as_bytes())?;
+            }
+            None => out.write_all(&[0])?,
+        }
+    }
+    out.write_all(&(named.len() as u64).to_le_bytes())?;
+    for (name, f) in named {
+        write_str(out, name.to_bytes())?;
+        out.write_all(&(f.size() as u64).to_le_bytes())?;
+        f.write_as_bytes(out)?;
+    }
+    Ok(())
+}
+
+fn read_archive(input: &mut impl std::io::Read) -> Result<Manager, String> {
+    fn read_bytes<const N: usize>(input: &mut impl std::io::Read) -> Result<[u8; N], String> {
+        let mut buf = [0; N];
+        input
+            .read_exact(&mut buf)
+            .map_err(|err| format!("Cannot read archive: {err}"))?;
+        Ok(buf)
+    }
+    fn read_str(input: &mut impl std::io::Read) -> Result<Vec<u8>, String> {
+        let len = u32::from_le_bytes(read_bytes(input)?) as usize;
+        let mut buf = Vec::new();
+        // Use `take()` to not allocate huge buffers for corrupted files
+        match input.by_ref().take(len as u64).read_to_end(&mut buf) {
+            Ok(n) if n == len => Ok(buf),
+            Ok(_) => Err("Unexpected end of archive".to_string()),
+            Err(err) => Err(format!("Cannot read archive: {err}")),
+        }
+    }
+
+    if &read_bytes(input)? != ARCHIVE_MAGIC {
+        return Err("Not a BDD archive".to_string());
+    }
+    let max_nodes_total = u64::from_le_bytes(read_bytes(input)?) as usize;
+    let num_vars = u16::from_le_bytes(read_bytes(input)?);
+    let mut var_names: Vec<Option<String>> = Vec::with_capacity(num_vars as usize);
+    for _ in 0..num_vars {
+        if read_bytes::<1>(input)? == [0] {
+            var_names.push(None);
+            continue;
+        }
+        let name = String::from_utf8(read_str(input)?)
+            .map_err(|_| "Variable name is not valid UTF-8".to_string())?;
+        if var_names.iter().flatten().any(|n| *n == name) {
+            return Err(format!("Duplicate variable name '{name}'"));
+        }
+        var_names.push(Some(name));
+    }
+    let c_var_names = var_names
+        .iter()
+        .map(|name| name.as_deref().map(CString::new).transpose())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Variable name contains a NUL byte".to_string())?;
+    let var_names = complete_var_names(var_names);
+    let var_names: Vec<&str> = var_names.iter().map(String::as_str).collect();
+    let mut m = Manager::new(BddVariableSet::new(&var_names), max_nodes_total);
+    m.var_names = c_var_names;
+
+    let num_named = u64::from_le_bytes(read_bytes(input)?);
+    for _ in 0..num_named {
+        let name = CString::new(read_str(input)?)
+            .map_err(|_| "BDD name contains a null byte".to_string())?;
+        let size = u64::from_le_bytes(read_bytes(input)?);
+        let nodes = Bdd::read_as_bytes(&mut input.by_ref().take(size.saturating_mul(10)))
+            .map_err(|err| format!("Cannot read archive: {err}"))?
+            .to_nodes();
+        if nodes.len() as u64 != size {
+            return Err("Unexpected end of archive".to_string());
+        }
+        let mut f = Bdd::from_nodes(&nodes).map_err(|err| format!("Invalid BDD: {err}"))?;
+        if nodes
+            .iter()
+            .skip(2)
+            .any(|n| n.var.to_index() >= num_vars as usize)
+        {
+            return Err(format!("BDD {name:?} uses undeclared variables"));
+        }
+        unsafe { f.set_num_vars(num_vars) };
+        m.named.push((name, f));
+    }
+    Ok(m)
+}
+
+/// Save `manager`'s variables and the BDDs `fs` with the given `names` to a
+/// single file
+///
+/// `names` and `fs` must point to `num` elements each, and all BDDs must belong
+/// to `manager`. Use `manager_load()` to restore the manager. Returns 0 on
+/// success and -1 on error.
+#[no_mangle]
+pub unsafe extern "C" fn manager_save(
+    manager: manager_t,
+    names: *const *const std::ffi::c_char,
+    fs: *const bdd_t,
+    num: usize,
+    path: *const std::ffi::c_char,
+) -> i32 {
+    guard(-1, || {
+        let m = unsafe { &*manager._p };
+        let names = unsafe { &*std::ptr::slice_from_raw_parts(names, num) };
+        let fs = unsafe { &*std::ptr::slice_from_raw_parts(fs, num) };
+        let mut named = Vec::with_capacity(num);
+        for (&name, f) in names.iter().zip(fs) {
+            let name = unsafe { std::ffi::CStr::from_ptr(name) };
+            let f = unsafe { &*f._p };
+            if f.manager != manager._p {
+                set_error(format!("BDD {name:?} belongs to a different manager"));
+                return -1;
+            }
+            let f = &f.bdd;
+            if f.support_set()
+                .iter()
+                .any(|v| v.to_index() >= m.num_vars() as usize)
+            {
+                set_error(format!("BDD {name:?} uses variables outside the manager"));
+                return -1;
+            }
+            named.push((name, f));
+        }
+
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => {
+                set_error(err);
+                return -1;
+            }
+        };
+        let res = std::fs::File::create(path).and_then(|file| {
+            let mut file = std::io::BufWriter::new(file);
+            write_archive(&mut file, m, &named)?;
+            std::io::Write::flush(&mut file)
+        });
+        match res {
+            Ok(()) => 0,
+            Err(err) => {
+                set_error(format!("Cannot write '{}': {err}", path.display()));
+                -1
+            }
+        }
+    })
+}
+
+/// Load a manager saved using `manager_save()`
+///
+/// The manager has the saved variable names (see `manager_var_name()`) and node
+/// limit. The saved BDDs can be retrieved using `manager_named_bdd()`. Returns
+/// a null manager if the file cannot be read.
+#[no_mangle]
+pub unsafe extern "C" fn manager_load(path: *const std::ffi::c_char) -> manager_t {
+    guard(manager_t::null(), || {
+        let path = match unsafe { c_path(path) } {
+            Ok(path) => path,
+            Err(err) => {
+                set_error(err);
+                return manager_t::null();
+            }
+        };
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(err) => {
+                set_error(format!("Cannot open '{}': {err}", path.display()));
+                return manager_t::null();
+            }
+        };
+        match read_archive(&mut std::io::BufReader::new(file)) {
+            Ok(m) => manager_t {
+                _p: Box::into_raw(Box::new(m)),
+            },
+            Err(err) => {
+                set_error(format!("Cannot load '{}': {err}", path.display()));
+                manager_t::null()
+            }
+        }
+    })
+}
+
+/// Number of named BDDs loaded by `manager_load()`
+#[no_mangle]
+pub unsafe extern "C" fn manager_named_count(manager: manager_t) -> usize {
+    guard(0, || unsafe { &*manager._p }.named.len())
+}
+
+/// Name of the `index`-th BDD loaded by `manager_load()`
+///
+/// The string is owned by the manager. Returns null if `index` is out of
+/// range.
+#[no_mangle]
+pub unsafe extern "C" fn manager_named_name(
+    manager: manager_t,
+    index: usize,
+) -> *const std::ffi::c_char {
+    guard(std::ptr::null(), || {
+        match unsafe { &*manager._p }.named.get(index) {
+            Some((name, _)) => name.as_ptr(),
+            None => std::ptr::null(),
+        }
+    })
+}
+
+/// Name of variable `i` of a manager loaded by `manager_load()` (or cloned from
+/// one)
+///
+/// The string is owned by the manager. Returns null if the variable is
+/// anonymous (e.g., the manager was created using `manager_new()`, or it was
+/// saved from such a manager) or `i` is out of range.
+#[no_mangle]
+pub unsafe extern "C" fn manager_var_name(manager: manager_t, i: u16) -> *const std::ffi::c_char {
+    guard(std::ptr::null(), || {
+        unsafe { &*manager._p }
+            .var_names
+            .get(i as usize)
+            .and_then(Option::as_ref)
+            .map_or(std::ptr::null(), |name| name.as_ptr())
+    })
+}
+
+/// Get a new handle for the BDD named `name` loaded by `manager_load()`
+///
+/// Returns a null handle if there is no such BDD. If several BDDs have the
+/// same name, the first one is returned.
+#[no_mangle]
+pub unsafe extern "C" fn manager_named_bdd(
+    manager: manager_t,
+    name: *const std::ffi::c_char,
+) -> bdd_t {
+    guard(bdd_t::null(), || {
+        let name = unsafe { std::ffi::CStr::from_ptr(name) };
+        match unsafe { &*manager._p }
+            .named
+            .iter()
+            .find(|(n, _)| n.as_c_str() == name)
+        {
+            Some((_, f)) => unsafe { bdd_t::from_bdd(f.clone(), manager._p) },
+            None => bdd_t::error(format!("No BDD named {name:?}")),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::mem::MaybeUninit;
+
+    /// Manager that releases the handles passed to `keep()` when dropped
+    struct Fixture {
+        m: manager_t,
+        handles: RefCell<Vec<bdd_t>>,
+    }
+
+    impl Fixture {
+        fn new(num_vars: u16) -> Self {
+            Self::with_manager(manager_new(num_vars, 1 << 20))
+        }
+
+        fn with_manager(m: manager_t) -> Self {
+            Fixture {
+                m,
+                handles: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn keep(&self, f: bdd_t) -> bdd_t {
+            self.handles.borrow_mut().push(f);
+            f
+        }
+
+        fn var(&self, i: u16) -> bdd_t {
+            self.keep(unsafe { manager_ithvar(self.m, i) })
+        }
+
+        fn nvar(&self, i: u16) -> bdd_t {
+            self.keep(unsafe { manager_nithvar(self.m, i) })
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            for &f in self.handles.borrow().iter() {
+                unsafe { bdd_unref(f) };
+            }
+            unsafe { manager_unref(self.m) };
+        }
+    }
+
+    /// Check `f` against `expected` on all assignments to its `n` variables
+    unsafe fn check_truth_table(f: bdd_t, n: u16, expected: impl Fn(u32) -> bool) {
+        for row in 0u32..1 << n {
+            let values: Vec<i8> = (0..n).map(|i| (row >> i & 1) as i8).collect();
+            let mut out = bdd_t::null();
+            let res = unsafe { bdd_eval_partial(f, values.as_ptr(), values.len(), &mut out) };
+            unsafe { bdd_unref(out) };
+            assert_eq!(res == 1, expected(row), "assignment {values:?}");
+        }
+    }
+
+    /// Copy the values of `assignment` and free it
+    unsafe fn take_assignment(assignment: bdd_assignment_t) -> Vec<i8> {
+        if assignment.data.is_null() {
+            return Vec::new();
+        }
+        let values =
+            unsafe { std::slice::from_raw_parts(assignment.data, assignment.len) }.to_vec();
+        unsafe { bdd_assignment_free(assignment) };
+        values
+    }
+
+    #[test]
+    fn simplify_agrees_on_care_set() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let x0_x1 = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let f = fx.keep(bdd_or(x0_x1, fx.var(2)));
+            let g = fx.keep(bdd_simplify(f, fx.nvar(2)));
+            assert!(bdd_eq(g, x0_x1));
+
+            let t = fx.keep(bdd_or(fx.var(0), fx.nvar(0)));
+            let g = fx.keep(bdd_simplify(f, t));
+            assert_eq!(g._p, f._p);
+        }
+    }
+
+    #[test]
+    fn cube_from_values() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let values = [1i8, -1, 0];
+            let f = fx.keep(manager_cube(fx.m, values.as_ptr(), values.len()));
+            assert!(bdd_eq(f, fx.keep(bdd_and(fx.var(0), fx.nvar(2)))));
+
+            let values = [-1i8; 3];
+            let f = fx.keep(manager_cube(fx.m, values.as_ptr(), values.len()));
+            assert!(bdd_eq(f, fx.keep(manager_true(fx.m))));
+            assert!(manager_cube(fx.m, values.as_ptr(), 2)._p.is_null());
+        }
+    }
+
+    #[test]
+    fn constants_are_shared() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let t = fx.keep(manager_true(fx.m));
+            let nodes = manager_node_count(fx.m);
+            assert_eq!(fx.keep(manager_true(fx.m))._p, t._p);
+            assert_eq!(manager_node_count(fx.m), nodes);
+            let f = fx.keep(manager_false(fx.m));
+            assert_eq!(fx.keep(manager_false(fx.m))._p, f._p);
+            assert_ne!(f._p, t._p);
+        }
+    }
+
+    extern "C" fn collect_literals(lits: *const i32, len: usize, ctx: *mut std::ffi::c_void) {
+        let cubes = unsafe { &mut *(ctx as *mut Vec<Vec<i32>>) };
+        cubes.push(unsafe { std::slice::from_raw_parts(lits, len) }.to_vec());
+    }
+
+    #[test]
+    fn dnf_and_cnf_literals() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let mut dnf: Vec<Vec<i32>> = Vec::new();
+            bdd_to_dnf(f, collect_literals, &mut dnf as *mut _ as *mut _);
+            assert_eq!(dnf, [vec![1, -3]]);
+            let mut cnf: Vec<Vec<i32>> = Vec::new();
+            bdd_to_cnf(f, collect_literals, &mut cnf as *mut _ as *mut _);
+            cnf.sort();
+            assert_eq!(cnf, [vec![-1, -3], vec![1]]);
+        }
+    }
+
+    #[test]
+    fn extend_and_transfer() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(1)));
+            let big = Fixture::with_manager(manager_extend(fx.m, 2));
+            assert_eq!((*big.m._p).var_set.num_vars(), 4);
+            let g = big.keep(bdd_transfer(f, big.m));
+            assert!(bdd_eq(g, big.keep(bdd_and(big.var(0), big.nvar(1)))));
+            assert_eq!(bdd_satcount(big.keep(bdd_and(g, big.var(3)))), 2.0);
+        }
+    }
+
+    #[test]
+    fn eval_partial_detects_constants() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let mut out = bdd_t::null();
+            assert_eq!(bdd_eval_partial(f, [0i8, -1].as_ptr(), 2, &mut out), 0);
+            assert!(bdd_eq(fx.keep(out), fx.keep(manager_false(fx.m))));
+            assert_eq!(bdd_eval_partial(f, [1i8, -1].as_ptr(), 2, &mut out), -1);
+            assert!(bdd_eq(fx.keep(out), fx.var(1)));
+            assert_eq!(bdd_eval_partial(f, [1i8, 1].as_ptr(), 2, &mut out), 1);
+            fx.keep(out);
+            check_truth_table(f, 2, |row| row == 0b11);
+        }
+    }
+
+    #[test]
+    fn pickcube_is_deterministic() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            assert_eq!(take_assignment(bdd_pickcube(f)), [0, 1]);
+            assert_eq!(take_assignment(bdd_pickcube_high_first(f)), [1, -1]);
+
+            let ff = fx.keep(manager_false(fx.m));
+            assert!(bdd_pickcube(ff).data.is_null());
+            assert!(bdd_pickcube_high_first(ff).data.is_null());
+        }
+    }
+
+    #[test]
+    fn essential_literals_of_cube_part() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let cube = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let f = fx.keep(bdd_and(cube, fx.keep(bdd_or(fx.var(1), fx.var(3)))));
+            assert_eq!(take_assignment(bdd_essential_literals(f)), [1, -1, 0, -1]);
+            let t = fx.keep(manager_true(fx.m));
+            assert_eq!(take_assignment(bdd_essential_literals(t)), [-1; 4]);
+            let ff = fx.keep(manager_false(fx.m));
+            assert_eq!(take_assignment(bdd_essential_literals(ff)), [-1; 4]);
+        }
+    }
+
+    #[test]
+    fn swap_vars_exchanges_variables() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.keep(bdd_and(fx.var(0), fx.nvar(1))), fx.var(2)));
+            let g = fx.keep(bdd_swap_vars(f, 0, 2));
+            let expected = fx.keep(bdd_or(fx.keep(bdd_and(fx.var(2), fx.nvar(1))), fx.var(0)));
+            assert!(bdd_eq(g, expected));
+            assert!(bdd_eq(fx.keep(bdd_swap_vars(g, 2, 0)), f));
+            assert!(bdd_eq(fx.keep(bdd_swap_vars(f, 1, 1)), f));
+        }
+    }
+
+    #[test]
+    fn last_error_after_failure() {
+        let fx = Fixture::new(2);
+        unsafe {
+            bdd_clear_error();
+            let values = [1i8];
+            let f = fx.keep(manager_cube(fx.m, values.as_ptr(), values.len()));
+            assert!(f._p.is_null());
+            assert!(!bdd_last_error_message().is_null());
+            bdd_clear_error();
+            assert!(bdd_last_error_message().is_null());
+        }
+    }
+
+    #[test]
+    fn manager_identity() {
+        let a = Fixture::new(2);
+        let b = Fixture::new(2);
+        unsafe {
+            assert!(manager_eq(a.m, a.m));
+            assert!(!manager_eq(a.m, b.m));
+            assert!(bdd_same_manager(a.var(0), a.var(1)));
+            assert!(!bdd_same_manager(a.var(0), b.var(0)));
+        }
+    }
+
+    #[test]
+    fn cofactor_counts_split_satcount() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let (mut t, mut e) = (0.0, 0.0);
+            bdd_cofactor_counts(f, 0, &mut t, &mut e);
+            assert_eq!((t, e), (4.0, 2.0));
+            bdd_cofactor_counts(f, 2, &mut t, &mut e);
+            assert_eq!((t, e), (3.0, 3.0));
+        }
+    }
+
+    #[test]
+    fn json_lists_nodes() {
+        let fx = Fixture::new(1);
+        unsafe {
+            let s = bdd_to_json(fx.var(0));
+            assert_eq!(
+                std::ffi::CStr::from_ptr(s).to_str().unwrap(),
+                r#"{"num_vars":1,"root":2,"terminals":[{"id":0,"value":false},{"id":1,"value":true}],"nodes":[{"id":2,"var":0,"low":0,"high":1}]}"#
+            );
+            bdd_string_free(s);
+        }
+    }
+
+    #[test]
+    fn truth_table_rows_and_limit() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let bits = [0b1110_1000u8];
+            let f = fx.keep(manager_from_truth_table(fx.m, bits.as_ptr(), 1, 3));
+            check_truth_table(f, 3, |row| row.count_ones() >= 2);
+
+            let large = Fixture::new(40);
+            let f = large.keep(manager_from_truth_table(
+                large.m,
+                bits.as_ptr(),
+                1 << 37,
+                40,
+            ));
+            assert!(f._p.is_null());
+            let small = Fixture::with_manager(manager_new(3, 4));
+            let f = small.keep(manager_from_truth_table(small.m, bits.as_ptr(), 1, 3));
+            assert!(f._p.is_null());
+        }
+    }
+
+    #[test]
+    fn not_inplace_reuses_unshared_handle() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let mut f = bdd_and(fx.var(0), fx.var(1));
+            let p = f._p;
+            bdd_not_inplace(&mut f);
+            assert_eq!(f._p, p);
+            assert!(bdd_eq(f, fx.keep(bdd_or(fx.nvar(0), fx.nvar(1)))));
+
+            let g = fx.keep(bdd_ref(f));
+            bdd_not_inplace(&mut f);
+            fx.keep(f);
+            assert_ne!(f._p, g._p);
+            assert!(bdd_eq(f, fx.keep(bdd_not(g))));
+        }
+    }
+
+    #[test]
+    fn pickcube_into_buffer() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let mut out = [9i8; 2];
+            assert_eq!(bdd_pickcube_into(f, out.as_mut_ptr(), 2), 1);
+            assert_eq!(out, [0, 1]);
+            assert_eq!(bdd_pickcube_into(f, out.as_mut_ptr(), 1), -1);
+            let ff = fx.keep(manager_false(fx.m));
+            assert_eq!(bdd_pickcube_into(ff, out.as_mut_ptr(), 2), 0);
+        }
+    }
+
+    #[test]
+    fn quantify_over_varset() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.keep(bdd_and(fx.var(0), fx.var(1))), fx.var(2)));
+            let vars = [0u16, 2];
+            let set = manager_varset(fx.m, vars.as_ptr(), vars.len());
+            assert!(bdd_eq(fx.keep(bdd_exists_set(f, set)), fx.var(1)));
+            let g = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            assert!(bdd_eq(fx.keep(bdd_forall_set(g, set)), fx.var(1)));
+            bdd_varset_free(set);
+            assert!(manager_varset(fx.m, [3u16].as_ptr(), 1)._p.is_null());
+        }
+    }
+
+    #[test]
+    fn eq_on_care_set_and_managers() {
+        let fx = Fixture::new(2);
+        let other = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            assert!(bdd_eq_on(f, fx.var(0), fx.var(1)));
+            assert!(!bdd_eq_on(f, fx.var(0), fx.keep(manager_true(fx.m))));
+
+            bdd_clear_error();
+            assert!(!bdd_eq_on(f, f, other.var(1)));
+            assert!(!bdd_last_error_message().is_null());
+            assert!(fx.keep(bdd_and(f, other.var(0)))._p.is_null());
+        }
+    }
+
+    #[test]
+    fn quantified_products_over_varset() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_or(fx.nvar(1), fx.var(2)));
+            let vars = [1u16];
+            let set = manager_varset(fx.m, vars.as_ptr(), vars.len());
+            assert!(bdd_eq(
+                fx.keep(bdd_and_exists_set(f, g, set)),
+                fx.keep(bdd_and_exists(f, g, vars.as_ptr(), vars.len()))
+            ));
+            assert!(bdd_eq(
+                fx.keep(bdd_or_exists_set(f, g, set)),
+                fx.keep(bdd_or_exists(f, g, vars.as_ptr(), vars.len()))
+            ));
+            bdd_varset_free(set);
+        }
+    }
+
+    extern "C" fn write_vec(data: *const u8, len: usize, ctx: *mut std::ffi::c_void) -> isize {
+        let buf = unsafe { &mut *(ctx as *mut Vec<u8>) };
+        // Consume at most 3 bytes per call to exercise the retry logic
+        let len = len.min(3);
+        buf.extend_from_slice(unsafe { std::slice::from_raw_parts(data, len) });
+        len as isize
+    }
+
+    extern "C" fn read_slice(buf: *mut u8, len: usize, ctx: *mut std::ffi::c_void) -> isize {
+        let input = unsafe { &mut *(ctx as *mut &[u8]) };
+        let len = len.min(input.len());
+        unsafe { std::ptr::copy_nonoverlapping(input.as_ptr(), buf, len) };
+        *input = &input[len..];
+        len as isize
+    }
+
+    extern "C" fn read_error(_: *mut u8, _: usize, _: *mut std::ffi::c_void) -> isize {
+        -1
+    }
+
+    #[test]
+    fn bytes_callback_round_trip() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_xor(fx.var(0), fx.nvar(2)));
+            let mut bytes: Vec<u8> = Vec::new();
+            assert_eq!(
+                bdd_to_bytes_cb(f, write_vec, &mut bytes as *mut _ as *mut _),
+                0
+            );
+            let mut input = &bytes[..];
+            let g = fx.keep(bdd_from_bytes_cb(
+                fx.m,
+                read_slice,
+                &mut input as *mut _ as *mut _,
+            ));
+            assert!(!g._p.is_null());
+            let mut loaded = (**g._p).clone();
+            loaded.set_num_vars(3);
+            assert_eq!(loaded, **f._p);
+
+            let g = fx.keep(bdd_from_bytes_cb(fx.m, read_error, std::ptr::null_mut()));
+            assert!(g._p.is_null());
+        }
+    }
+
+    #[test]
+    fn depends_on_support() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(2)));
+            assert!(bdd_depends_on(f, 0));
+            assert!(!bdd_depends_on(f, 1));
+            assert!(bdd_depends_on(f, 2));
+            assert!(!bdd_depends_on(fx.keep(manager_true(fx.m)), 0));
+        }
+    }
+
+    #[test]
+    fn stats_count_operations() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let (x0, x1) = (fx.var(0), fx.var(1));
+            manager_reset_stats(fx.m);
+            let f = fx.keep(bdd_and(x0, x1));
+            fx.keep(bdd_or(f, x1));
+            fx.keep(bdd_not(f));
+            fx.keep(bdd_swap_vars(f, 0, 2));
+            fx.keep(bdd_simplify(f, x0));
+            let mut out = bdd_t::null();
+            bdd_eval_partial(f, [1i8, -1, -1].as_ptr(), 3, &mut out);
+            fx.keep(out);
+            let mut stats = manager_stats_t::default();
+            manager_stats(fx.m, &mut stats);
+            assert_eq!((stats.and, stats.or, stats.not), (1, 1, 1));
+            assert_eq!(stats.rename, 1);
+            assert_eq!(stats.cofactor, 2);
+            assert!(stats.nodes_created > 0);
+            assert!(stats.peak_nodes >= manager_node_count(fx.m));
+
+            manager_reset_stats(fx.m);
+            manager_stats(fx.m, &mut stats);
+            assert_eq!(stats.and, 0);
+        }
+    }
+
+    #[test]
+    fn random_sat_is_seeded() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let a = take_assignment(bdd_random_sat(f, 42));
+            assert_eq!(a, take_assignment(bdd_random_sat(f, 42)));
+            let mut out = bdd_t::null();
+            assert_eq!(bdd_eval_partial(f, a.as_ptr(), a.len(), &mut out), 1);
+            fx.keep(out);
+            let ff = fx.keep(manager_false(fx.m));
+            assert!(bdd_random_sat(ff, 42).data.is_null());
+        }
+    }
+
+    #[test]
+    fn and_satcount_matches_and() {
+        let fx = Fixture::new(3);
+        let other = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_or(fx.nvar(1), fx.var(2)));
+            let nodes = manager_node_count(fx.m);
+            let count = bdd_and_satcount(f, g);
+            assert_eq!(manager_node_count(fx.m), nodes);
+            assert_eq!(count, bdd_satcount(fx.keep(bdd_and(f, g))));
+            assert!(bdd_and_satcount(f, other.var(0)).is_nan());
+        }
+    }
+
+    #[test]
+    fn repeated_and_hits_cache() {
+        let fx = Fixture::new(3);
+        unsafe {
+            manager_set_cache_size(fx.m, 16);
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_or(fx.nvar(1), fx.var(2)));
+            let (mut hits, mut misses) = (0, 0);
+            manager_reset_stats(fx.m);
+            let h1 = fx.keep(bdd_and(f, g));
+            let h2 = fx.keep(bdd_and(f, g));
+            assert!(bdd_eq(h1, h2));
+            manager_cache_stats(fx.m, &mut hits, &mut misses);
+            assert_eq!((hits, misses), (1, 1));
+
+            manager_reset_stats(fx.m);
+            manager_cache_stats(fx.m, &mut hits, &mut misses);
+            assert_eq!((hits, misses), (0, 0));
+        }
+    }
+
+    #[test]
+    fn cofactor_const_reports_constants() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let mut out = bdd_t::null();
+            assert_eq!(bdd_cofactor_const(f, 0, false, &mut out), 0);
+            assert!(bdd_eq(fx.keep(out), fx.keep(manager_false(fx.m))));
+            assert_eq!(bdd_cofactor_const(f, 0, true, &mut out), -1);
+            assert!(bdd_eq(fx.keep(out), fx.var(1)));
+        }
+    }
+
+    /// Manager with the variables `names`
+    fn named_manager(names: &[&str]) -> manager_t {
+        let mut manager = Manager::new(BddVariableSet::new(names), 1 << 20);
+        manager.var_names = names.iter().map(|&name| CString::new(name).ok()).collect();
+        manager_t {
+            _p: Box::into_raw(Box::new(manager)),
+        }
+    }
+
+    #[test]
+    fn archive_round_trip() {
+        let fx = Fixture::with_manager(named_manager(&["a", "b", "c"]));
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let name = CString::new("f").unwrap();
+            let path = std::env::temp_dir().join(format!("archive-{}.bin", std::process::id()));
+            let c_path = CString::new(path.to_str().unwrap()).unwrap();
+            assert_eq!(
+                manager_save(fx.m, &name.as_ptr(), &f, 1, c_path.as_ptr()),
+                0
+            );
+            let loaded = Fixture::with_manager(manager_load(c_path.as_ptr()));
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(manager_named_count(loaded.m), 1);
+            let g = loaded.keep(manager_named_bdd(loaded.m, name.as_ptr()));
+            assert!(bdd_eq(
+                g,
+                loaded.keep(bdd_and(loaded.var(0), loaded.nvar(2)))
+            ));
+
+            let extended = Fixture::with_manager(manager_extend(loaded.m, 2));
+            let names = &(*extended.m._p).var_set;
+            let names: Vec<String> = names
+                .variables()
+                .into_iter()
+                .map(|v| names.name_of(v))
+                .collect();
+            assert_eq!(names, ["a", "b", "c", "x_3", "x_4"]);
+
+            let other = Fixture::new(3);
+            let h = other.var(0);
+            assert_eq!(
+                manager_save(fx.m, &name.as_ptr(), &h, 1, c_path.as_ptr()),
+                -1
+            );
+        }
+    }
+
+    #[test]
+    fn path_count_ignores_skipped_variables() {
+        let fx = Fixture::new(3);
+        unsafe {
+            assert_eq!(bdd_path_count(fx.keep(manager_false(fx.m))), 0);
+            assert_eq!(bdd_path_count(fx.keep(manager_true(fx.m))), 1);
+            assert_eq!(bdd_path_count(fx.keep(bdd_or(fx.var(0), fx.var(2)))), 2);
+            assert_eq!(bdd_path_count(fx.keep(bdd_xor(fx.var(0), fx.var(1)))), 2);
+        }
+    }
+
+    #[test]
+    fn nth_sat_in_lexicographic_order() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let mut out = [9i8; 2];
+            for (i, expected) in [[0, 1], [1, 0], [1, 1]].iter().enumerate() {
+                assert!(bdd_nth_sat(f, i as f64, out.as_mut_ptr(), 2));
+                assert_eq!(&out, expected);
+            }
+            assert!(!bdd_nth_sat(f, 3.0, out.as_mut_ptr(), 2));
+        }
+    }
+
+    #[test]
+    fn permute_identity_and_inverse() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.keep(bdd_and(fx.var(0), fx.nvar(1))), fx.var(2)));
+            let id = [0u16, 1, 2];
+            assert!(bdd_eq(fx.keep(bdd_permute(f, id.as_ptr(), 3)), f));
+
+            let perm = [2u16, 0, 1];
+            let inverse = [1u16, 2, 0];
+            let g = fx.keep(bdd_permute(f, perm.as_ptr(), 3));
+            let expected = fx.keep(bdd_or(fx.keep(bdd_and(fx.var(2), fx.nvar(0))), fx.var(1)));
+            assert!(bdd_eq(g, expected));
+            assert!(bdd_eq(fx.keep(bdd_permute(g, inverse.as_ptr(), 3)), f));
+
+            let invalid = [0u16, 0, 1];
+            assert!(fx.keep(bdd_permute(f, invalid.as_ptr(), 3))._p.is_null());
+        }
+    }
+
+    #[test]
+    fn node_stats_of_conjunction() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let mut stats = MaybeUninit::<bdd_node_stats_t>::uninit();
+            bdd_node_stats(f, stats.as_mut_ptr());
+            let stats = stats.assume_init();
+            assert_eq!(stats.terminal_nodes, 2);
+            assert_eq!(stats.internal_nodes, 2);
+            assert_eq!(stats.terminal_refs, 3);
+            assert_eq!(stats.nodes_with_one_terminal_child, 1);
+            assert_eq!(stats.nodes_with_two_terminal_children, 1);
+        }
+    }
+
+    #[test]
+    fn save_load_non_ascii_path() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_xor(fx.var(0), fx.nvar(2)));
+            let dir = std::env::temp_dir().join(format!("lib-bdd-ffi-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("bdd-ü-∀.bin");
+            let c_path = CString::new(path.to_str().unwrap()).unwrap();
+            assert_eq!(bdd_save(f, c_path.as_ptr()), 0);
+            let g = fx.keep(bdd_load(fx.m, c_path.as_ptr()));
+            std::fs::remove_dir_all(&dir).unwrap();
+            assert!(!g._p.is_null());
+            // `bdd_load()` sets the maximal variable count
+            let mut loaded = (**g._p).clone();
+            loaded.set_num_vars(3);
+            assert_eq!(loaded, **f._p);
+        }
+    }
+
+    #[test]
+    fn save_to_missing_directory() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let path = std::env::temp_dir().join("lib-bdd-ffi-missing-dir/f.bin");
+            let c_path = CString::new(path.to_str().unwrap()).unwrap();
+            assert_eq!(bdd_save(fx.var(1), c_path.as_ptr()), -1);
+        }
+    }
+
+    extern "C" fn collect_cube(values: *const i8, len: usize, ctx: *mut std::ffi::c_void) -> bool {
+        let cubes = unsafe { &mut *(ctx as *mut Vec<Vec<i8>>) };
+        cubes.push(unsafe { std::slice::from_raw_parts(values, len) }.to_vec());
+        true
+    }
+
+    extern "C" fn first_cube(_: *const i8, _: usize, ctx: *mut std::ffi::c_void) -> bool {
+        unsafe { *(ctx as *mut usize) += 1 };
+        false
+    }
+
+    #[test]
+    fn prime_implicants_of_multiplexer() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_ite(fx.var(0), fx.var(1), fx.var(2)));
+            let mut primes: Vec<Vec<i8>> = Vec::new();
+            bdd_prime_implicants(f, collect_cube, &mut primes as *mut _ as *mut _);
+            primes.sort();
+            assert_eq!(primes, [vec![-1, 1, 1], vec![0, -1, 1], vec![1, 1, -1]]);
+
+            let mut calls = 0usize;
+            bdd_prime_implicants(f, first_cube, &mut calls as *mut _ as *mut _);
+            assert_eq!(calls, 1);
+        }
+    }
+
+    #[test]
+    fn majority_truth_table() {
+        let fx = Fixture::new(4);
+        let other = Fixture::new(3);
+        unsafe {
+            let x: Vec<bdd_t> = (0..4).map(|i| fx.var(i)).collect();
+            let f = fx.keep(bdd_majority(x[0], x[1], x[2]));
+            check_truth_table(f, 4, |row| (row & 0b111).count_ones() > 3 / 2);
+
+            // Strict majority of 4 inputs: if `x3` holds, 2 of the others
+            // suffice, otherwise all 3 are needed.
+            let all = fx.keep(bdd_and(fx.keep(bdd_and(x[0], x[1])), x[2]));
+            let f = fx.keep(bdd_ite(x[3], f, all));
+            check_truth_table(f, 4, |row| row.count_ones() > 4 / 2);
+
+            assert!(fx.keep(bdd_majority(x[0], x[1], other.var(2)))._p.is_null());
+        }
+    }
+
+    extern "C" {
+        fn malloc(size: usize) -> *mut std::ffi::c_void;
+    }
+
+    #[test]
+    fn assignment_array_free_skips_null() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let ff = fx.keep(manager_false(fx.m));
+            let arr = malloc(3 * std::mem::size_of::<bdd_assignment_t>()) as *mut bdd_assignment_t;
+            arr.write(bdd_pickcube(f));
+            arr.add(1).write(bdd_pickcube(ff));
+            arr.add(2).write(bdd_pickcube_high_first(f));
+            assert!((*arr.add(1)).data.is_null());
+            bdd_assignment_array_free(arr, 3);
+        }
+    }
+
+    #[test]
+    fn dedup_shares_equal_handles() {
+        let fx = Fixture::new(2);
+        let other = Fixture::new(2);
+        unsafe {
+            let (x0, x1) = (fx.var(0), fx.var(1));
+            let mut fs = [
+                bdd_and(x0, x1),
+                bdd_or(x0, x1),
+                bdd_and(x1, x0),
+                bdd_t::null(),
+            ];
+            assert_ne!(fs[0]._p, fs[2]._p);
+            assert_eq!(bdd_dedup(fs.as_mut_ptr(), fs.len()), 2);
+            assert_eq!(fs[0]._p, fs[2]._p);
+            for f in fs {
+                fx.keep(f);
+            }
+
+            let mut fs = [x0, other.var(0)];
+            bdd_clear_error();
+            assert_eq!(bdd_dedup(fs.as_mut_ptr(), fs.len()), usize::MAX);
+            assert!(!bdd_last_error_message().is_null());
+        }
+    }
+
+    #[test]
+    fn unsat_count_near_tautology() {
+        let fx = Fixture::new(60);
+        unsafe {
+            let mut f = fx.keep(manager_false(fx.m));
+            for i in 0..60 {
+                f = fx.keep(bdd_or(f, fx.var(i)));
+            }
+            assert_eq!(bdd_unsat_count(f), 1.0);
+            assert_eq!(bdd_unsat_count(f), bdd_satcount(fx.keep(bdd_not(f))));
+            assert_eq!(bdd_unsat_count(fx.keep(manager_true(fx.m))), 0.0);
+            assert_eq!(bdd_unsat_count(fx.keep(manager_false(fx.m))), 2f64.powi(60));
+        }
+    }
+
+    #[test]
+    fn shift_vars_moves_support() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(1)));
+            let g = fx.keep(bdd_shift_vars(f, 2));
+            assert!(bdd_eq(g, fx.keep(bdd_and(fx.var(2), fx.nvar(3)))));
+            assert!(bdd_eq(fx.keep(bdd_shift_vars(g, -2)), f));
+            assert!(fx.keep(bdd_shift_vars(f, -1))._p.is_null());
+            assert!(fx.keep(bdd_shift_vars(f, 3))._p.is_null());
+        }
+    }
+
+    #[test]
+    fn satcount_log2_of_wide_manager() {
+        let fx = Fixture::new(3);
+        let wide = Fixture::new(2000);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            assert_eq!(bdd_satcount_log2(f), 6f64.log2());
+            assert_eq!(
+                bdd_satcount_log2(fx.keep(manager_false(fx.m))),
+                f64::NEG_INFINITY
+            );
+            assert_eq!(bdd_satcount_log2(wide.keep(manager_true(wide.m))), 2000.0);
+            assert_eq!(bdd_satcount_log2(wide.var(7)), 1999.0);
+        }
+    }
+
+    #[test]
+    fn set_verbose() {
+        let fx = Fixture::new(1);
+        unsafe {
+            assert!((*fx.m._p).verbose);
+            manager_set_verbose(fx.m, false);
+            assert!(!(*fx.m._p).verbose);
+            let extended = Fixture::with_manager(manager_extend(fx.m, 1));
+            assert!(!(*extended.m._p).verbose);
+        }
+    }
+
+    #[test]
+    fn mixed_quant_exists_before_forall() {
+        let fx = Fixture::new(2);
+        let other = Fixture::new(2);
+        unsafe {
+            // f ∧ g = x0 ↔ x1
+            let f = fx.keep(bdd_or(fx.var(0), fx.nvar(1)));
+            let g = fx.keep(bdd_or(fx.nvar(0), fx.var(1)));
+            let (x0, x1) = ([0u16], [1u16]);
+            let h = fx.keep(bdd_and_mixed_quant(f, g, x0.as_ptr(), 1, x1.as_ptr(), 1));
+            assert!(bdd_eq(h, fx.keep(manager_true(fx.m))));
+            let h = fx.keep(bdd_and_mixed_quant(f, g, x0.as_ptr(), 1, x0.as_ptr(), 1));
+            assert!(h._p.is_null());
+            let h = fx.keep(bdd_and_mixed_quant(
+                f,
+                other.var(1),
+                x0.as_ptr(),
+                1,
+                x1.as_ptr(),
+                1,
+            ));
+            assert!(h._p.is_null());
+        }
+    }
+
+    #[test]
+    fn single_cube() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let cube = fx.keep(bdd_and(fx.keep(bdd_and(fx.var(0), fx.nvar(1))), fx.var(2)));
+            assert!(bdd_is_single_cube(cube));
+            assert!(!bdd_is_single_cube(fx.keep(bdd_or(fx.var(0), fx.var(1)))));
+            assert!(bdd_is_single_cube(fx.keep(manager_true(fx.m))));
+            assert!(!bdd_is_single_cube(fx.keep(manager_false(fx.m))));
+        }
+    }
+
+    #[test]
+    fn sat_to_csv_rows() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(2)));
+            let path = std::env::temp_dir().join(format!("sat-{}.csv", std::process::id()));
+            let c_path = CString::new(path.to_str().unwrap()).unwrap();
+            assert_eq!(bdd_sat_to_csv(f, c_path.as_ptr(), 0), 3);
+            let csv = std::fs::read_to_string(&path).unwrap();
+            let mut lines: Vec<&str> = csv.lines().collect();
+            lines[1..].sort();
+            assert_eq!(lines, ["x_0,x_2", "0,1", "1,0", "1,1"]);
+            assert_eq!(bdd_sat_to_csv(f, c_path.as_ptr(), 2), 2);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn cube_cover_is_disjoint_and_complete() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let or = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let maj = fx.keep(bdd_majority(fx.var(0), fx.var(1), fx.var(2)));
+            for f in [or, maj] {
+                let mut cubes: Vec<Vec<i8>> = Vec::new();
+                bdd_cube_cover(f, collect_cube, &mut cubes as *mut _ as *mut _);
+                let cubes: Vec<bdd_t> = cubes
+                    .iter()
+                    .map(|c| fx.keep(manager_cube(fx.m, c.as_ptr(), c.len())))
+                    .collect();
+                let mut union = fx.keep(manager_false(fx.m));
+                for (i, &c) in cubes.iter().enumerate() {
+                    for &d in &cubes[..i] {
+                        assert!(bdd_eq(fx.keep(bdd_and(c, d)), fx.keep(manager_false(fx.m))));
+                    }
+                    union = fx.keep(bdd_or(union, c));
+                }
+                assert!(bdd_eq(union, f));
+            }
+
+            let mut calls = 0usize;
+            bdd_cube_cover(or, first_cube, &mut calls as *mut _ as *mut _);
+            assert_eq!(calls, 1);
+        }
+    }
+
+    #[test]
+    fn eval_tree_postfix() {
+        let fx = Fixture::new(2);
+        let other = Fixture::new(2);
+        unsafe {
+            let ops = [BDD_OP_PUSH, BDD_OP_PUSH, BDD_OP_NOT, BDD_OP_AND];
+            let args = [fx.var(0), fx.var(1)];
+            let f = fx.keep(bdd_eval_tree(
+                ops.as_ptr(),
+                args.as_ptr(),
+                ops.len(),
+                args.len(),
+            ));
+            assert!(bdd_eq(f, fx.keep(bdd_and(fx.var(0), fx.nvar(1)))));
+
+            let f = fx.keep(bdd_eval_tree(ops.as_ptr(), args.as_ptr(), 3, args.len()));
+            assert!(f._p.is_null());
+
+            let args = [fx.var(0), other.var(1)];
+            let f = fx.keep(bdd_eval_tree(
+                ops.as_ptr(),
+                args.as_ptr(),
+                ops.len(),
+                args.len(),
+            ));
+            assert!(f._p.is_null());
+        }
+    }
+
+    #[test]
+    fn satcount_projected_onto_vars() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let vars = [0u16, 2];
+            assert_eq!(bdd_satcount_projected(f, vars.as_ptr(), vars.len()), 2.0);
+            let vars = [0u16, 3];
+            assert!(bdd_satcount_projected(f, vars.as_ptr(), vars.len()).is_nan());
+        }
+    }
+
+    #[test]
+    fn from_nodes_validates_table() {
+        let fx = Fixture::new(2);
+        unsafe {
+            // Node 2 is x1, node 3 is x0 ∧ x1
+            let f = fx.keep(manager_from_nodes(
+                fx.m,
+                [1u16, 0].as_ptr(),
+                [0u32, 0].as_ptr(),
+                [1u32, 2].as_ptr(),
+                2,
+                3,
+            ));
+            assert!(bdd_eq(f, fx.keep(bdd_and(fx.var(0), fx.var(1)))));
+
+            let f = fx.keep(manager_from_nodes(
+                fx.m,
+                [0u16, 0].as_ptr(),
+                [0u32, 0].as_ptr(),
+                [1u32, 2].as_ptr(),
+                2,
+                3,
+            ));
+            assert!(f._p.is_null());
+            let f = fx.keep(manager_from_nodes(
+                fx.m,
+                [1u16].as_ptr(),
+                [1u32].as_ptr(),
+                [1u32].as_ptr(),
+                1,
+                2,
+            ));
+            assert!(f._p.is_null());
+        }
+    }
+
+    #[test]
+    fn shortest_cube_has_fewest_literals() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let x123 = fx.keep(bdd_and(fx.keep(bdd_and(fx.var(1), fx.var(2))), fx.var(3)));
+            let f = fx.keep(bdd_or(fx.var(0), x123));
+            assert_eq!(take_assignment(bdd_shortest_cube(f)), [1, -1, -1, -1]);
+            assert!(bdd_shortest_cube(fx.keep(manager_false(fx.m)))
+                .data
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn xor_cube_toggles_region() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let values = [1i8, 1];
+            let f = fx.keep(bdd_xor_cube(fx.var(0), values.as_ptr(), values.len()));
+            assert!(bdd_eq(f, fx.keep(bdd_and(fx.var(0), fx.nvar(1)))));
+            assert!(fx
+                .keep(bdd_xor_cube(fx.var(0), values.as_ptr(), 1))
+                ._p
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn support_shared_and_combined() {
+        let fx = Fixture::new(4);
+        let other = Fixture::new(4);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_or(fx.var(1), fx.var(2)));
+            assert_eq!(take_assignment(bdd_support_shared(f, g)), [-1, 1, -1, -1]);
+            assert_eq!(take_assignment(bdd_support_combined(f, g)), [1, 1, 1, -1]);
+            assert!(bdd_support_shared(f, other.var(1)).data.is_null());
+        }
+    }
+
+    #[test]
+    fn panic_becomes_error() {
+        let fx = Fixture::new(3);
+        unsafe {
+            bdd_clear_error();
+            let f = fx.keep(manager_ithvar(fx.m, 10));
+            assert!(f._p.is_null());
+            assert!(!bdd_last_error_message().is_null());
+            bdd_clear_error();
+            assert!(bdd_last_error_message().is_null());
+        }
+    }
+
+    #[test]
+    fn constrain_agrees_on_constraint() {
+        let fx = Fixture::new(3);
+        let other = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let c = fx.keep(bdd_xor(fx.var(0), fx.var(2)));
+            let g = fx.keep(bdd_constrain(f, c));
+            assert!(bdd_eq(fx.keep(bdd_and(g, c)), fx.keep(bdd_and(f, c))));
+            assert!(bdd_eq(
+                fx.keep(bdd_constrain(f, fx.keep(manager_true(fx.m)))),
+                f
+            ));
+            let ff = fx.keep(manager_false(fx.m));
+            assert!(bdd_eq(fx.keep(bdd_constrain(f, ff)), ff));
+            assert!(fx.keep(bdd_constrain(f, other.var(0)))._p.is_null());
+        }
+    }
+
+    #[test]
+    fn rename_variables_checked_accepts_order_preserving() {
+        let fx = Fixture::new(5);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let pairs = [
+                VarPair {
+                    first: 0,
+                    second: 1,
+                },
+                VarPair {
+                    first: 2,
+                    second: 4,
+                },
+            ];
+            let g = fx.keep(bdd_rename_variables_checked(f, pairs.as_ptr(), pairs.len()));
+            assert!(!g._p.is_null());
+            assert!(bdd_eq(
+                g,
+                fx.keep(bdd_rename_variables(f, pairs.as_ptr(), pairs.len()))
+            ));
+            assert!(bdd_eq(g, fx.keep(bdd_and(fx.var(1), fx.nvar(4)))));
+        }
+    }
+
+    #[test]
+    fn rename_variables_checked_rejects_reordering() {
+        let fx = Fixture::new(5);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let pairs = [VarPair {
+                first: 0,
+                second: 3,
+            }];
+            bdd_clear_error();
+            let g = fx.keep(bdd_rename_variables_checked(f, pairs.as_ptr(), pairs.len()));
+            assert!(g._p.is_null());
+            assert!(!bdd_last_error_message().is_null());
+        }
+    }
+
+    #[test]
+    fn cofactor_histogram_per_variable() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.keep(bdd_and(fx.var(0), fx.var(1))), fx.var(2)));
+            let mut out = [9usize; 3];
+            assert_eq!(bdd_cofactor_histogram(f, out.as_mut_ptr(), 3), 5);
+            assert_eq!(out, [1, 1, 1]);
+            assert_eq!(bdd_cofactor_histogram(f, out.as_mut_ptr(), 2), 0);
+        }
+    }
+
+    #[test]
+    fn all_equal_of_three() {
+        let fx = Fixture::new(3);
+        let other = Fixture::new(3);
+        unsafe {
+            let fs: Vec<bdd_t> = (0..3).map(|i| fx.var(i)).collect();
+            let f = fx.keep(bdd_all_equal(fs.as_ptr(), fs.len()));
+            check_truth_table(f, 3, |row| row == 0 || row == 0b111);
+            assert!(bdd_eq(
+                fx.keep(bdd_all_equal(fs.as_ptr(), 1)),
+                fx.keep(manager_true(fx.m))
+            ));
+            assert!(fx.keep(bdd_all_equal(fs.as_ptr(), 0))._p.is_null());
+            let fs = [fs[0], other.var(1)];
+            assert!(fx.keep(bdd_all_equal(fs.as_ptr(), fs.len()))._p.is_null());
+        }
+    }
+
+    #[test]
+    fn at_least_and_at_most_k() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let vars = [0u16, 1, 2];
+            let f = fx.keep(manager_at_least_k(fx.m, vars.as_ptr(), vars.len(), 2));
+            check_truth_table(f, 4, |row| (row & 0b111).count_ones() >= 2);
+            let f = fx.keep(manager_at_most_k(fx.m, vars.as_ptr(), vars.len(), 1));
+            check_truth_table(f, 4, |row| (row & 0b111).count_ones() <= 1);
+            let dup = [0u16, 0];
+            assert!(fx
+                .keep(manager_at_least_k(fx.m, dup.as_ptr(), dup.len(), 1))
+                ._p
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn exactly_and_between() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let vars = [0u16, 1, 2];
+            let f = fx.keep(manager_exactly_k(fx.m, vars.as_ptr(), vars.len(), 2));
+            check_truth_table(f, 4, |row| (row & 0b111).count_ones() == 2);
+            let f = fx.keep(manager_between(fx.m, vars.as_ptr(), vars.len(), 1, 2));
+            check_truth_table(f, 4, |row| (1..=2).contains(&(row & 0b111).count_ones()));
+            assert!(fx
+                .keep(manager_between(fx.m, vars.as_ptr(), vars.len(), 2, 1))
+                ._p
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn as_literal_detects_literals() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let (mut var, mut phase) = (9, false);
+            assert!(bdd_as_literal(fx.var(1), &mut var, &mut phase));
+            assert_eq!((var, phase), (1, true));
+            assert!(bdd_as_literal(fx.nvar(2), &mut var, &mut phase));
+            assert_eq!((var, phase), (2, false));
+            assert!(!bdd_as_literal(
+                fx.keep(bdd_and(fx.var(0), fx.var(1))),
+                &mut var,
+                &mut phase
+            ));
+            assert!(!bdd_as_literal(
+                fx.keep(manager_true(fx.m)),
+                &mut var,
+                &mut phase
+            ));
+        }
+    }
+
+    #[test]
+    fn expression_is_truncated() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let render = |f, max_len| {
+                let s = bdd_to_expression_truncated(f, max_len);
+                let res = std::ffi::CStr::from_ptr(s).to_str().unwrap().to_string();
+                bdd_string_free(s);
+                res
+            };
+            assert_eq!(render(f, 100), "(x_0 & x_1)");
+            assert_eq!(render(f, 4), "(x_0…");
+            assert_eq!(render(fx.keep(manager_true(fx.m)), 0), "true");
+        }
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let fx = Fixture::new(2);
+        unsafe {
+            fx.keep(bdd_and(fx.var(0), fx.var(1)));
+            let clone = Fixture::with_manager(manager_clone(fx.m));
+            assert!(!manager_eq(fx.m, clone.m));
+            assert_eq!(manager_node_count(clone.m), 0);
+            let f = clone.keep(bdd_and(clone.var(0), clone.var(1)));
+            assert!(bdd_same_manager(f, clone.var(0)));
+            assert!(!bdd_same_manager(f, fx.var(0)));
+        }
+    }
+
+    #[test]
+    fn sop_stats_counts_cubes_and_literals() {
+        let fx = Fixture::new(2);
+        unsafe {
+            let (mut cubes, mut literals) = (9, 9);
+            bdd_sop_stats(
+                fx.keep(bdd_or(fx.var(0), fx.var(1))),
+                &mut cubes,
+                &mut literals,
+            );
+            assert_eq!((cubes, literals), (2, 3));
+            bdd_sop_stats(fx.keep(manager_false(fx.m)), &mut cubes, &mut literals);
+            assert_eq!((cubes, literals), (0, 0));
+            bdd_sop_stats(fx.keep(manager_true(fx.m)), &mut cubes, &mut literals);
+            assert_eq!((cubes, literals), (1, 0));
+        }
+    }
+
+    #[test]
+    fn setminus_is_and_not() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_xor(fx.var(1), fx.var(2)));
+            let expected = fx.keep(bdd_and(f, fx.keep(bdd_not(g))));
+            assert!(bdd_eq(fx.keep(bdd_setminus(f, g)), expected));
+            let expected = fx.keep(bdd_and(g, fx.keep(bdd_not(f))));
+            assert!(bdd_eq(fx.keep(bdd_setminus(g, f)), expected));
+        }
+    }
+
+    #[test]
+    fn xor_satcount_matches_xor() {
+        let fx = Fixture::new(3);
+        let other = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            let g = fx.keep(bdd_and(fx.var(1), fx.var(2)));
+            assert_eq!(bdd_xor_satcount(f, g), bdd_satcount(fx.keep(bdd_xor(f, g))));
+            assert!(bdd_xor_satcount(f, other.var(0)).is_nan());
+        }
+    }
+
+    #[test]
+    fn var_names_survive_save_load() {
+        let anonymous = Fixture::new(2);
+        let named = Fixture::with_manager(named_manager(&["a", "b"]));
+        let path = std::env::temp_dir().join(format!("names-{}.bin", std::process::id()));
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        unsafe {
+            for fx in [&anonymous, &named] {
+                let f = fx.var(1);
+                let name = CString::new("f").unwrap();
+                assert_eq!(
+                    manager_save(fx.m, &name.as_ptr(), &f, 1, c_path.as_ptr()),
+                    0
+                );
+                let loaded = Fixture::with_manager(manager_load(c_path.as_ptr()));
+                for i in 0..2 {
+                    let (a, b) = (manager_var_name(fx.m, i), manager_var_name(loaded.m, i));
+                    assert_eq!(a.is_null(), b.is_null());
+                    if !a.is_null() {
+                        assert_eq!(std::ffi::CStr::from_ptr(a), std::ffi::CStr::from_ptr(b));
+                    }
+                }
+            }
+            std::fs::remove_file(&path).unwrap();
+            assert!(manager_var_name(anonymous.m, 0).is_null());
+            assert!(!manager_var_name(named.m, 0).is_null());
+            let extended = Fixture::with_manager(manager_extend(named.m, 1));
+            assert!(!manager_var_name(extended.m, 1).is_null());
+            assert!(manager_var_name(extended.m, 2).is_null());
+        }
+    }
+
+    #[test]
+    fn to_order_inverts_permute() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_and(fx.var(0), fx.nvar(1)));
+            let order = [2u16, 0, 1];
+            let g = fx.keep(bdd_permute(f, order.as_ptr(), order.len()));
+            assert!(bdd_eq(
+                fx.keep(bdd_to_order(g, order.as_ptr(), order.len())),
+                f
+            ));
+            let invalid = [0u16, 1, 1];
+            assert!(fx
+                .keep(bdd_to_order(f, invalid.as_ptr(), invalid.len()))
+                ._p
+                .is_null());
+        }
+    }
+
+    #[test]
+    fn satcount_given_partial_assignment() {
+        let fx = Fixture::new(3);
+        unsafe {
+            let f = fx.keep(bdd_or(fx.var(0), fx.var(1)));
+            assert_eq!(bdd_satcount_given(f, [0i8, -1, -1].as_ptr(), 3), 2.0);
+            assert_eq!(bdd_satcount_given(f, [1i8, -1, -1].as_ptr(), 3), 4.0);
+            assert!(bdd_satcount_given(f, [1i8, -1].as_ptr(), 2).is_nan());
+        }
+    }
+
+    #[test]
+    fn implied_cube_of_function() {
+        let fx = Fixture::new(4);
+        unsafe {
+            let cube = fx.keep(bdd_and(fx.var(0), fx.nvar(2)));
+            let f = fx.keep(bdd_and(cube, fx.keep(bdd_or(fx.var(1), fx.var(3)))));
+            assert!(bdd_eq(fx.keep(bdd_implied_cube(f)), cube));
+            let t = fx.keep(manager_true(fx.m));
+            assert!(bdd_eq(
+                fx.keep(bdd_implied_cube(fx.keep(bdd_or(fx.var(0), fx.var(1))))),
+                t
+            ));
+        }
+    }
+
+    #[test]
+    fn dimacs_import() {
+        let fx = Fixture::new(3);
+        let path = std::env::temp_dir().join(format!("cnf-{}.cnf", std::process::id()));
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        unsafe {
+            std::fs::write(&path, "c example\np cnf 3 2\n1 -2 0\n2 3 0\n").unwrap();
+            let f = fx.keep(manager_from_dimacs(fx.m, c_path.as_ptr()));
+            let expected = fx.keep(bdd_and(
+                fx.keep(bdd_or(fx.var(0), fx.nvar(1))),
+                fx.keep(bdd_or(fx.var(1), fx.var(2))),
+            ));
+            assert!(bdd_eq(f, expected));
+
+            std::fs::write(&path, "p cnf 4 1\n4 0\n").unwrap();
+            assert!(fx
+                .keep(manager_from_dimacs(fx.m, c_path.as_ptr()))
+                ._p
+                .is_null());
+            std::fs::write(&path, "p cnf 3 1\n1 x 0\n").unwrap();
+            assert!(fx
+                .keep(manager_from_dimacs(fx.m, c_path.as_ptr()))
+                ._p
+                .is_null());
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn density_of_small_and_wide_functions() {
+        let fx = Fixture::new(2);
+        let wide = Fixture::new(2000);
+        unsafe {
+            assert_eq!(bdd_density(fx.keep(bdd_or(fx.var(0), fx.var(1)))), 0.75);
+            assert_eq!(bdd_density(fx.keep(manager_false(fx.m))), 0.0);
+            assert_eq!(bdd_density(wide.var(1999)), 0.5);
+        }
+    }
 }